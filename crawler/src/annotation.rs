@@ -0,0 +1,143 @@
+use entity::{ip, ip_annotation, prelude::*};
+use sea_orm::{ActiveModelTrait, ActiveValue, ColumnTrait, EntityTrait, ModelTrait, QueryFilter};
+use std::{collections::HashMap, net::Ipv4Addr};
+
+use crate::{now, Crawler, CrawlerError};
+
+const TAG_SEPARATOR: &str = ",";
+
+/// Local tags and notes of an ip, e.g. `vpn, pentest` for a known egress
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Annotation {
+    pub tags: Vec<String>,
+    pub note: Option<String>,
+}
+
+impl Annotation {
+    /// Splits comma separated tags, empty and duplicate tags are dropped
+    pub fn parse_tags(tags: &str) -> Vec<String> {
+        let mut parsed: Vec<String> = Vec::new();
+        for tag in tags.split(TAG_SEPARATOR).map(str::trim) {
+            if !tag.is_empty() && !parsed.iter().any(|parsed_tag| parsed_tag == tag) {
+                parsed.push(tag.to_owned());
+            }
+        }
+        parsed
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|own_tag| own_tag == tag)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty() && self.note.as_deref().unwrap_or_default().is_empty()
+    }
+}
+
+fn saved_to_annotation(saved_annotation: ip_annotation::Model) -> Annotation {
+    Annotation {
+        tags: Annotation::parse_tags(&saved_annotation.tags),
+        note: saved_annotation.note,
+    }
+}
+
+impl Crawler {
+    pub(crate) async fn saved_annotation(
+        &self,
+        ipv4: &Ipv4Addr,
+    ) -> Result<Option<Annotation>, CrawlerError> {
+        let saved_annotation = IpAnnotation::find()
+            .inner_join(Ip)
+            .filter(ip::Column::Ipv4.eq(&*ipv4.to_string()))
+            .one(&self.db)
+            .await?;
+        Ok(saved_annotation.map(saved_to_annotation))
+    }
+
+    pub(crate) async fn saved_annotations(
+        &self,
+    ) -> Result<HashMap<Ipv4Addr, Annotation>, CrawlerError> {
+        let annotations = IpAnnotation::find()
+            .find_also_related(Ip)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .filter_map(|(saved_annotation, saved_ip)| {
+                let ipv4 = saved_ip?.ipv4.parse().ok()?;
+                Some((ipv4, saved_to_annotation(saved_annotation)))
+            })
+            .collect();
+        Ok(annotations)
+    }
+
+    /// Empty annotations remove the saved one
+    pub(crate) async fn upsert_annotation(
+        &self,
+        ipv4: &Ipv4Addr,
+        annotation: Annotation,
+    ) -> Result<(), CrawlerError> {
+        if annotation.is_empty() {
+            self.remove_annotation(ipv4).await?;
+            return Ok(());
+        }
+        let db_ip = self.find_or_create_ip(ipv4).await?;
+        let tags = annotation.tags.join(TAG_SEPARATOR);
+        let note = annotation.note.filter(|note| !note.is_empty());
+        match db_ip.find_related(IpAnnotation).one(&self.db).await? {
+            Some(saved_annotation) => {
+                let mut annotation_to_update: ip_annotation::ActiveModel = saved_annotation.into();
+                annotation_to_update.tags = ActiveValue::Set(tags);
+                annotation_to_update.note = ActiveValue::Set(note);
+                annotation_to_update.updated = ActiveValue::Set(Some(now()));
+                annotation_to_update.update(&self.db).await?;
+            }
+            None => {
+                let annotation_to_save = ip_annotation::ActiveModel {
+                    ip_id: ActiveValue::Set(db_ip.id),
+                    tags: ActiveValue::Set(tags),
+                    note: ActiveValue::Set(note),
+                    ..Default::default()
+                };
+                annotation_to_save.insert(&self.db).await?;
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn remove_annotation(&self, ipv4: &Ipv4Addr) -> Result<bool, CrawlerError> {
+        let Some(saved_ip) = Ip::find()
+            .filter(ip::Column::Ipv4.eq(&*ipv4.to_string()))
+            .one(&self.db)
+            .await?
+        else {
+            return Ok(false);
+        };
+        let deleted = IpAnnotation::delete_many()
+            .filter(ip_annotation::Column::IpId.eq(saved_ip.id))
+            .exec(&self.db)
+            .await?;
+        Ok(deleted.rows_affected > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_tags() {
+        assert_eq!(
+            Annotation::parse_tags(" vpn, known bad,,vpn "),
+            vec!["vpn".to_owned(), "known bad".to_owned()]
+        );
+    }
+
+    #[test]
+    fn should_treat_blank_annotations_as_empty() {
+        let annotation = Annotation {
+            tags: vec![],
+            note: Some(String::new()),
+        };
+        assert!(annotation.is_empty());
+    }
+}
@@ -0,0 +1,104 @@
+use chrono::NaiveDateTime;
+use entity::{ban, ip, prelude::*};
+use sea_orm::{ActiveValue, ColumnTrait, EntityTrait, QueryFilter, TransactionTrait};
+use std::{collections::HashMap, net::Ipv4Addr, time::Duration};
+
+use crate::{is_due, now, Crawler, CrawlerError};
+
+/// Firewall ban of an ip, lifted once it expired
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BanRecord {
+    /// Firewall backend which applied the ban, e.g. `nftables`
+    pub backend: String,
+    pub expires: NaiveDateTime,
+    pub created: NaiveDateTime,
+}
+
+impl BanRecord {
+    pub fn is_expired(&self) -> bool {
+        is_due(self.expires)
+    }
+}
+
+impl Crawler {
+    pub(crate) async fn saved_bans(&self) -> Result<HashMap<Ipv4Addr, BanRecord>, CrawlerError> {
+        let bans = Ban::find()
+            .find_also_related(Ip)
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .filter_map(|(saved_ban, saved_ip)| {
+                let ipv4 = saved_ip?.ipv4.parse().ok()?;
+                Some((
+                    ipv4,
+                    BanRecord {
+                        backend: saved_ban.backend,
+                        expires: saved_ban.expires,
+                        created: saved_ban.created,
+                    },
+                ))
+            })
+            .collect();
+        Ok(bans)
+    }
+
+    /// Replaces earlier bans of the ips
+    pub(crate) async fn upsert_bans(
+        &self,
+        ips: &[Ipv4Addr],
+        backend: &str,
+        duration: Duration,
+    ) -> Result<(), CrawlerError> {
+        if ips.is_empty() {
+            return Ok(());
+        }
+        let expires = chrono::Duration::from_std(duration)
+            .ok()
+            .and_then(|duration| now().checked_add_signed(duration))
+            .unwrap_or(NaiveDateTime::MAX);
+        let ipv4s: Vec<String> = ips.iter().map(Ipv4Addr::to_string).collect();
+        self.save_missing_ips(&ipv4s).await?;
+        let ip_ids: Vec<i32> = Ip::find()
+            .filter(ip::Column::Ipv4.is_in(ipv4s))
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|saved_ip| saved_ip.id)
+            .collect();
+        let bans_to_save: Vec<ban::ActiveModel> = ip_ids
+            .iter()
+            .map(|ip_id| ban::ActiveModel {
+                ip_id: ActiveValue::Set(*ip_id),
+                backend: ActiveValue::Set(backend.to_owned()),
+                expires: ActiveValue::Set(expires),
+                ..Default::default()
+            })
+            .collect();
+        let txn = self.db.begin().await?;
+        Ban::delete_many()
+            .filter(ban::Column::IpId.is_in(ip_ids))
+            .exec(&txn)
+            .await?;
+        Ban::insert_many(bans_to_save)
+            .exec_without_returning(&txn)
+            .await?;
+        txn.commit().await?;
+        Ok(())
+    }
+
+    pub(crate) async fn remove_bans(&self, ips: &[Ipv4Addr]) -> Result<u64, CrawlerError> {
+        let ipv4s: Vec<String> = ips.iter().map(Ipv4Addr::to_string).collect();
+        let ip_ids: Vec<i32> = Ip::find()
+            .filter(ip::Column::Ipv4.is_in(ipv4s))
+            .all(&self.db)
+            .await?
+            .into_iter()
+            .map(|saved_ip| saved_ip.id)
+            .collect();
+        let deleted = Ban::delete_many()
+            .filter(ban::Column::IpId.is_in(ip_ids))
+            .exec(&self.db)
+            .await?;
+        Ok(deleted.rows_affected)
+    }
+}
@@ -1,7 +1,20 @@
+use chrono::NaiveDateTime;
+use std::net::Ipv4Addr;
+
 #[derive(thiserror::Error, Debug)]
 pub enum CrawlerError {
     #[error("Failed to interact with shodan")]
     ScannerInteraction(#[from] ip_geolocation::IpScannerError),
+    #[error("Failed to look up the ip reputation")]
+    ReputationLookup(#[from] ip_reputation::IpReputationError),
+    #[error("Failed to enrich the ip with reverse DNS and RDAP data")]
+    EnrichmentLookup(#[from] ip_enrichment::IpEnrichmentError),
+    #[error("No enrichment scanner configured")]
+    EnrichmentDisabled,
+    #[error("{0} is allowlisted or private and never looked up")]
+    Excluded(Ipv4Addr),
+    #[error("Lookup failed before, next retry at {0}")]
+    LookupBackoff(NaiveDateTime),
     #[error("Failed to interact with the database")]
     DbInteraction(#[from] sea_orm::DbErr),
 }
@@ -0,0 +1,258 @@
+use std::{
+    collections::HashSet,
+    net::Ipv4Addr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+use tokio::sync::{mpsc, Semaphore};
+
+use crate::{
+    AppCrawler, CrawlerError, Enrichment, GeolocationRecord, Reputation, GEOLOCATION_BATCH_SIZE,
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Progress {
+    pub queued: usize,
+    pub done: usize,
+    pub failed: usize,
+}
+
+/// Outcome of all lookups for a single ip, emitted once per queued ip
+#[derive(Debug)]
+pub struct LookupResult {
+    pub ipv4: Ipv4Addr,
+    pub geolocation: Result<(f64, f64), CrawlerError>,
+    /// Latest saved geolocation with country and organisation, if the lookup succeeded
+    pub location: Option<GeolocationRecord>,
+    pub reputation: Result<Reputation, CrawlerError>,
+    pub enrichment: Result<Enrichment, CrawlerError>,
+    pub progress: Progress,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    queued: AtomicUsize,
+    done: AtomicUsize,
+    failed: AtomicUsize,
+}
+
+impl Counters {
+    fn snapshot(&self) -> Progress {
+        Progress {
+            queued: self.queued.load(Ordering::SeqCst),
+            done: self.done.load(Ordering::SeqCst),
+            failed: self.failed.load(Ordering::SeqCst),
+        }
+    }
+}
+
+/// Deduplicated work queue that looks up every ip once with bounded concurrency
+pub struct WorkQueue {
+    sender: mpsc::UnboundedSender<Ipv4Addr>,
+    seen: Arc<Mutex<HashSet<Ipv4Addr>>>,
+    failed: Arc<Mutex<HashSet<Ipv4Addr>>>,
+    counters: Arc<Counters>,
+}
+
+impl WorkQueue {
+    /// Spawns the workers on the current tokio runtime
+    pub fn spawn<C>(
+        crawler: Arc<C>,
+        concurrency: usize,
+    ) -> (Self, mpsc::UnboundedReceiver<LookupResult>)
+    where
+        C: AppCrawler + Send + Sync + 'static,
+    {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let (result_sender, result_receiver) = mpsc::unbounded_channel();
+        let seen = Arc::new(Mutex::new(HashSet::new()));
+        let failed = Arc::new(Mutex::new(HashSet::new()));
+        let counters = Arc::new(Counters::default());
+        tokio::spawn(run_workers(
+            crawler,
+            concurrency.max(1),
+            receiver,
+            result_sender,
+            seen.clone(),
+            failed.clone(),
+            counters.clone(),
+        ));
+        let queue = Self {
+            sender,
+            seen,
+            failed,
+            counters,
+        };
+        (queue, result_receiver)
+    }
+
+    /// Returns `false` if the ip was queued before, excluded ips are dropped by the workers
+    /// and can be queued again once they were removed from the allowlist
+    pub fn push(&self, ipv4: Ipv4Addr) -> bool {
+        if !self.seen.lock().unwrap().insert(ipv4) {
+            return false;
+        }
+        self.counters.queued.fetch_add(1, Ordering::SeqCst);
+        self.sender.send(ipv4).is_ok()
+    }
+
+    /// Returns the amount of newly queued ips
+    pub fn extend<I: IntoIterator<Item = Ipv4Addr>>(&self, ips: I) -> usize {
+        ips.into_iter().filter(|ipv4| self.push(*ipv4)).count()
+    }
+
+    /// Queues ips with a failed geolocation again, the crawler skips them
+    /// until their lookup backoff expired
+    pub fn retry_failed(&self) -> usize {
+        let failed: Vec<Ipv4Addr> = {
+            // The workers count failures while holding the lock, so only counted ones are drained
+            let mut failed = self.failed.lock().unwrap();
+            self.counters.done.fetch_sub(failed.len(), Ordering::SeqCst);
+            self.counters
+                .failed
+                .fetch_sub(failed.len(), Ordering::SeqCst);
+            failed.drain().collect()
+        };
+        failed
+            .into_iter()
+            .filter(|ipv4| self.sender.send(*ipv4).is_ok())
+            .count()
+    }
+
+    pub fn progress(&self) -> Progress {
+        self.counters.snapshot()
+    }
+}
+
+async fn run_workers<C>(
+    crawler: Arc<C>,
+    concurrency: usize,
+    mut receiver: mpsc::UnboundedReceiver<Ipv4Addr>,
+    result_sender: mpsc::UnboundedSender<LookupResult>,
+    seen: Arc<Mutex<HashSet<Ipv4Addr>>>,
+    failed: Arc<Mutex<HashSet<Ipv4Addr>>>,
+    counters: Arc<Counters>,
+) where
+    C: AppCrawler + Send + Sync + 'static,
+{
+    let semaphore = Arc::new(Semaphore::new(concurrency));
+    while let Some(ipv4) = receiver.recv().await {
+        // Ips queued together, like the ones of the read logs, are geolocated in one batch
+        let mut batch = vec![ipv4];
+        while batch.len() < GEOLOCATION_BATCH_SIZE {
+            let Ok(ipv4) = receiver.try_recv() else {
+                break;
+            };
+            batch.push(ipv4);
+        }
+        batch.retain(|ipv4| {
+            let excluded = crawler.is_excluded(ipv4);
+            if excluded {
+                seen.lock().unwrap().remove(ipv4);
+                counters.queued.fetch_sub(1, Ordering::SeqCst);
+            }
+            !excluded
+        });
+        // Ips missing from the batch are looked up on their own, which reports why they failed
+        let mut located = crawler.geolocations(&batch).await.unwrap_or_default();
+        for ipv4 in batch {
+            let Ok(permit) = semaphore.clone().acquire_owned().await else {
+                return;
+            };
+            let located = located.remove(&ipv4);
+            let crawler = crawler.clone();
+            let result_sender = result_sender.clone();
+            let failed = failed.clone();
+            let counters = counters.clone();
+            tokio::spawn(async move {
+                let geolocation = match located {
+                    Some(location) => Ok(location),
+                    None => crawler.geolocation(&ipv4).await,
+                };
+                let location = match geolocation {
+                    Ok(_) => crawler
+                        .geolocation_history(&ipv4)
+                        .await
+                        .ok()
+                        .and_then(|history| history.into_iter().next()),
+                    Err(_) => None,
+                };
+                let reputation = crawler.reputation(&ipv4).await;
+                let enrichment = crawler.enrichment(&ipv4).await;
+                if geolocation.is_err() {
+                    let mut failed = failed.lock().unwrap();
+                    failed.insert(ipv4);
+                    counters.failed.fetch_add(1, Ordering::SeqCst);
+                    counters.done.fetch_add(1, Ordering::SeqCst);
+                } else {
+                    counters.done.fetch_add(1, Ordering::SeqCst);
+                }
+                // The receiver is gone if the app shuts down
+                let _ = result_sender.send(LookupResult {
+                    ipv4,
+                    geolocation,
+                    location,
+                    reputation,
+                    enrichment,
+                    progress: counters.snapshot(),
+                });
+                drop(permit);
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Crawler;
+    use async_trait::async_trait;
+    use ip_geolocation::{Geolocation, GeolocationProvider, IpScannerError};
+    use std::time::Duration;
+
+    /// Fails every lookup without a request, like shodan for an unknown ip
+    struct NoDataProvider;
+
+    #[async_trait]
+    impl GeolocationProvider for NoDataProvider {
+        async fn ip_geolocation(&self, _ipv4: &Ipv4Addr) -> Result<Geolocation, IpScannerError> {
+            Err(IpScannerError::NoData(
+                "No information available".to_owned(),
+            ))
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn should_keep_progress_within_queued() {
+        // Retries fail fast during the backoff of the first failure
+        let crawler = Crawler::new("sqlite::memory:", "")
+            .await
+            .unwrap()
+            .with_geolocation_provider(Box::new(NoDataProvider));
+        let (queue, mut results) = WorkQueue::spawn(Arc::new(crawler), 4);
+        assert_eq!(
+            queue.extend((1..=8).map(|host| Ipv4Addr::new(1, 0, 0, host))),
+            8
+        );
+        let receive_results = async {
+            let mut received = 0;
+            while received < 200 {
+                queue.retry_failed();
+                let progress = queue.progress();
+                assert!(progress.done <= progress.queued, "{:?}", progress);
+                assert!(progress.failed <= progress.queued, "{:?}", progress);
+                if let Ok(result) = results.try_recv() {
+                    assert!(result.geolocation.is_err());
+                    assert!(result.progress.done <= result.progress.queued);
+                    received += 1;
+                }
+                tokio::task::yield_now().await;
+            }
+        };
+        tokio::time::timeout(Duration::from_secs(30), receive_results)
+            .await
+            .expect("Should receive the lookup results");
+    }
+}
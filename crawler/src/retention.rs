@@ -0,0 +1,114 @@
+use chrono::{Months, Utc};
+use entity::{geolocation, ip_daily_activity, prelude::*, ssh_event};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseBackend, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, TransactionTrait,
+};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::{Crawler, CrawlerError};
+
+/// Keeps the bound parameters of a single delete below sqlite's limit
+const PRUNE_BATCH_SIZE: usize = 1000;
+
+/// How long saved data is kept before [`crate::AppCrawler::prune`] removes it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct RetentionPolicy {
+    /// Days raw ssh events are kept
+    pub event_days: u32,
+    /// Months the per day aggregates of the events are kept
+    pub daily_activity_months: u32,
+    /// Amount of geolocation lookups kept per ip
+    pub geolocation_history: usize,
+    /// Reclaims the freed space of a sqlite database after pruning
+    pub vacuum: bool,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            event_days: 30,
+            daily_activity_months: 12,
+            geolocation_history: 10,
+            vacuum: true,
+        }
+    }
+}
+
+/// Amount of removed rows per table
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PruneReport {
+    pub events: u64,
+    pub daily_activities: u64,
+    pub geolocations: u64,
+}
+
+impl Crawler {
+    pub(crate) async fn prune_saved(&self) -> Result<PruneReport, CrawlerError> {
+        let now = Utc::now().naive_utc();
+        let events_before = now - chrono::Duration::days(self.retention.event_days.into());
+        let days_before = now
+            .date()
+            .checked_sub_months(Months::new(self.retention.daily_activity_months))
+            .unwrap_or_default();
+
+        let txn = self.db.begin().await?;
+        let events = ssh_event::Entity::delete_many()
+            .filter(ssh_event::Column::Timestamp.lt(events_before))
+            .exec(&txn)
+            .await?
+            .rows_affected;
+        let daily_activities = IpDailyActivity::delete_many()
+            .filter(ip_daily_activity::Column::Day.lt(days_before))
+            .exec(&txn)
+            .await?
+            .rows_affected;
+        let geolocations = self.prune_geolocation_history(&txn).await?;
+        txn.commit().await?;
+
+        if self.retention.vacuum && self.db.get_database_backend() == DatabaseBackend::Sqlite {
+            self.db.execute_unprepared("VACUUM").await?;
+        }
+        Ok(PruneReport {
+            events,
+            daily_activities,
+            geolocations,
+        })
+    }
+
+    /// Removes all but the latest lookups of every ip
+    async fn prune_geolocation_history<C: ConnectionTrait>(
+        &self,
+        conn: &C,
+    ) -> Result<u64, CrawlerError> {
+        let saved_locations: Vec<(i32, i32)> = Geolocation::find()
+            .select_only()
+            .column(geolocation::Column::Id)
+            .column(geolocation::Column::IpId)
+            .order_by_desc(geolocation::Column::Id)
+            .into_tuple()
+            .all(conn)
+            .await?;
+        let mut kept_per_ip: HashMap<i32, usize> = HashMap::new();
+        let ids_to_delete: Vec<i32> = saved_locations
+            .into_iter()
+            .filter(|(_, ip_id)| {
+                let kept = kept_per_ip.entry(*ip_id).or_default();
+                *kept += 1;
+                *kept > self.retention.geolocation_history
+            })
+            .map(|(id, _)| id)
+            .collect();
+        let mut deleted = 0;
+        for ids in ids_to_delete.chunks(PRUNE_BATCH_SIZE) {
+            deleted += Geolocation::delete_many()
+                .filter(geolocation::Column::Id.is_in(ids.to_owned()))
+                .exec(conn)
+                .await?
+                .rows_affected;
+        }
+        Ok(deleted)
+    }
+}
@@ -0,0 +1,30 @@
+use entity::{ban, geolocation, ip, ip_annotation, ip_daily_activity, lookup_failure, ssh_event};
+use sea_orm::{EntityTrait, PaginatorTrait};
+
+use crate::{Crawler, CrawlerError};
+
+/// Amount of saved rows per table
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DatabaseStats {
+    pub ips: u64,
+    pub geolocations: u64,
+    pub events: u64,
+    pub daily_activities: u64,
+    pub annotations: u64,
+    pub bans: u64,
+    pub lookup_failures: u64,
+}
+
+impl Crawler {
+    pub(crate) async fn count_saved(&self) -> Result<DatabaseStats, CrawlerError> {
+        Ok(DatabaseStats {
+            ips: ip::Entity::find().count(&self.db).await?,
+            geolocations: geolocation::Entity::find().count(&self.db).await?,
+            events: ssh_event::Entity::find().count(&self.db).await?,
+            daily_activities: ip_daily_activity::Entity::find().count(&self.db).await?,
+            annotations: ip_annotation::Entity::find().count(&self.db).await?,
+            bans: ban::Entity::find().count(&self.db).await?,
+            lookup_failures: lookup_failure::Entity::find().count(&self.db).await?,
+        })
+    }
+}
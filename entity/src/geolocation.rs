@@ -9,8 +9,11 @@ pub struct Model {
     pub id: i32,
     pub latitude: f64,
     pub longitude: f64,
-    pub created: String,
+    pub created: DateTime,
     pub ip_id: i32,
+    pub country_name: Option<String>,
+    pub org: Option<String>,
+    pub asn: Option<String>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -8,14 +8,33 @@ pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
     pub ipv4: String,
-    pub created: String,
-    pub updated: Option<String>,
+    pub created: DateTime,
+    pub updated: Option<DateTime>,
+    pub ptr: Option<String>,
+    pub rdap_network_name: Option<String>,
+    pub rdap_cidr: Option<String>,
+    pub rdap_abuse_email: Option<String>,
+    pub enriched: Option<DateTime>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
     #[sea_orm(has_many = "super::geolocation::Entity")]
     Geolocation,
+    #[sea_orm(has_many = "super::reputation::Entity")]
+    Reputation,
+    #[sea_orm(has_one = "super::lookup_failure::Entity")]
+    LookupFailure,
+    #[sea_orm(has_many = "super::ssh_event::Entity")]
+    SshEvent,
+    #[sea_orm(has_one = "super::ip_activity::Entity")]
+    IpActivity,
+    #[sea_orm(has_many = "super::ip_daily_activity::Entity")]
+    IpDailyActivity,
+    #[sea_orm(has_one = "super::ip_annotation::Entity")]
+    IpAnnotation,
+    #[sea_orm(has_one = "super::ban::Entity")]
+    Ban,
 }
 
 impl Related<super::geolocation::Entity> for Entity {
@@ -24,4 +43,46 @@ impl Related<super::geolocation::Entity> for Entity {
     }
 }
 
+impl Related<super::reputation::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Reputation.def()
+    }
+}
+
+impl Related<super::lookup_failure::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::LookupFailure.def()
+    }
+}
+
+impl Related<super::ssh_event::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::SshEvent.def()
+    }
+}
+
+impl Related<super::ip_activity::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::IpActivity.def()
+    }
+}
+
+impl Related<super::ip_daily_activity::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::IpDailyActivity.def()
+    }
+}
+
+impl Related<super::ip_annotation::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::IpAnnotation.def()
+    }
+}
+
+impl Related<super::ban::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Ban.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -2,5 +2,12 @@
 
 pub mod prelude;
 
+pub mod ban;
 pub mod geolocation;
 pub mod ip;
+pub mod ip_activity;
+pub mod ip_annotation;
+pub mod ip_daily_activity;
+pub mod lookup_failure;
+pub mod reputation;
+pub mod ssh_event;
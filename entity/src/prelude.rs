@@ -1,4 +1,11 @@
 //! `SeaORM` Entity. Generated by sea-orm-codegen 0.10.5
 
+pub use super::ban::Entity as Ban;
 pub use super::geolocation::Entity as Geolocation;
 pub use super::ip::Entity as Ip;
+pub use super::ip_activity::Entity as IpActivity;
+pub use super::ip_annotation::Entity as IpAnnotation;
+pub use super::ip_daily_activity::Entity as IpDailyActivity;
+pub use super::lookup_failure::Entity as LookupFailure;
+pub use super::reputation::Entity as Reputation;
+pub use super::ssh_event::Entity as SshEvent;
@@ -0,0 +1,9 @@
+#[derive(thiserror::Error, Debug)]
+pub enum IpBlockingError {
+    #[error("`{command}` failed: {stderr}")]
+    CommandFailed { command: String, stderr: String },
+    #[error("Failed to run the firewall command or update the hosts file")]
+    Io(#[from] std::io::Error),
+    #[error("Failed to read the fail2ban database")]
+    Fail2banDb(#[from] sea_orm::DbErr),
+}
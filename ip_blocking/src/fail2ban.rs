@@ -0,0 +1,192 @@
+use sea_orm::{ConnectionTrait, Database, DatabaseConnection, Statement};
+use std::{
+    net::Ipv4Addr,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{Action, IpBlockingError};
+
+pub const DEFAULT_DATABASE: &str = "/var/lib/fail2ban/fail2ban.sqlite3";
+const DEFAULT_CLIENT: &str = "fail2ban-client";
+/// Current bans, the `bips` table exists since fail2ban 0.11
+const CURRENT_BANS_QUERY: &str = "SELECT ip, jail, timeofban, bantime FROM bips";
+
+/// Ban of an ip in a fail2ban jail
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fail2banBan {
+    pub ipv4: Ipv4Addr,
+    pub jail: String,
+    /// Seconds since the unix epoch
+    pub banned_at: i64,
+    /// Seconds since the unix epoch, permanent bans have none
+    pub until: Option<i64>,
+}
+
+impl Fail2banBan {
+    pub fn is_active(&self, now: i64) -> bool {
+        self.until.filter(|until| *until <= now).is_none()
+    }
+}
+
+/// Read-only view of the fail2ban database
+pub struct Fail2banDb {
+    db: DatabaseConnection,
+}
+
+impl Fail2banDb {
+    pub async fn open<P: AsRef<Path>>(path: P) -> Result<Self, IpBlockingError> {
+        let url = format!("sqlite://{}?mode=ro", path.as_ref().display());
+        let db = Database::connect(url).await?;
+        Ok(Self { db })
+    }
+
+    /// Bans of ipv4s which are not lifted yet
+    pub async fn active_bans(&self) -> Result<Vec<Fail2banBan>, IpBlockingError> {
+        self.active_bans_at(unix_now()).await
+    }
+
+    pub async fn active_bans_at(&self, now: i64) -> Result<Vec<Fail2banBan>, IpBlockingError> {
+        let backend = self.db.get_database_backend();
+        let rows = self
+            .db
+            .query_all(Statement::from_string(backend, CURRENT_BANS_QUERY))
+            .await?;
+        let bans = rows
+            .into_iter()
+            .filter_map(|row| {
+                let ipv4 = row.try_get::<String>("", "ip").ok()?.parse().ok()?;
+                let banned_at: i64 = row.try_get("", "timeofban").ok()?;
+                let bantime: i64 = row.try_get("", "bantime").ok()?;
+                Some(Fail2banBan {
+                    ipv4,
+                    jail: row.try_get("", "jail").ok()?,
+                    banned_at,
+                    // Negative ban times never expire
+                    until: (bantime >= 0).then_some(banned_at + bantime),
+                })
+            })
+            .filter(|ban| ban.is_active(now))
+            .collect();
+        Ok(bans)
+    }
+}
+
+/// Bans and unbans through the running fail2ban server
+#[derive(Debug, Clone)]
+pub struct Fail2banClient {
+    binary: PathBuf,
+}
+
+impl Default for Fail2banClient {
+    fn default() -> Self {
+        Self::new(DEFAULT_CLIENT)
+    }
+}
+
+impl Fail2banClient {
+    pub fn new<P: AsRef<Path>>(binary: P) -> Self {
+        Self {
+            binary: binary.as_ref().to_owned(),
+        }
+    }
+
+    pub fn ban_action(&self, jail: &str, ips: &[Ipv4Addr]) -> Action {
+        self.jail_action(jail, "banip", ips)
+    }
+
+    pub fn unban_action(&self, jail: &str, ips: &[Ipv4Addr]) -> Action {
+        self.jail_action(jail, "unbanip", ips)
+    }
+
+    pub fn ban(&self, jail: &str, ips: &[Ipv4Addr]) -> Result<(), IpBlockingError> {
+        self.ban_action(jail, ips).apply()
+    }
+
+    pub fn unban(&self, jail: &str, ips: &[Ipv4Addr]) -> Result<(), IpBlockingError> {
+        self.unban_action(jail, ips).apply()
+    }
+
+    /// `fail2ban-client set <jail> banip|unbanip <ip>...`
+    fn jail_action(&self, jail: &str, command: &str, ips: &[Ipv4Addr]) -> Action {
+        let mut words = vec![
+            self.binary.display().to_string(),
+            "set".to_owned(),
+            jail.to_owned(),
+            command.to_owned(),
+        ];
+        words.extend(ips.iter().map(Ipv4Addr::to_string));
+        Action::Command(words)
+    }
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|now| now.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures");
+
+    /// Copies a fixture into its own directory, tests must not touch the originals
+    fn copy_fixture(name: &str, test: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("ip_blocking_{}", test));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        fs::copy(Path::new(FIXTURES).join(name), &path).unwrap();
+        path
+    }
+
+    #[tokio::test]
+    async fn should_read_active_bans() {
+        let path = copy_fixture("fail2ban.sqlite3", "active_bans");
+        let db = Fail2banDb::open(&path).await.unwrap();
+        let mut bans = db.active_bans_at(1_700_000_300).await.unwrap();
+        bans.sort_by_key(|ban| ban.ipv4);
+        assert_eq!(
+            bans,
+            vec![
+                Fail2banBan {
+                    ipv4: Ipv4Addr::new(198, 51, 100, 23),
+                    jail: "recidive".to_owned(),
+                    banned_at: 1_700_000_000,
+                    until: None,
+                },
+                Fail2banBan {
+                    ipv4: Ipv4Addr::new(203, 0, 113, 7),
+                    jail: "sshd".to_owned(),
+                    banned_at: 1_700_000_000,
+                    until: Some(1_700_000_600),
+                },
+            ]
+        );
+        assert_eq!(db.active_bans_at(1_700_000_600).await.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn should_ban_and_unban_through_the_client() {
+        let binary = copy_fixture("fail2ban-client", "client");
+        let log = binary.with_extension("log");
+        let _ = fs::remove_file(&log);
+        let client = Fail2banClient::new(&binary);
+        let ips = [Ipv4Addr::new(203, 0, 113, 7), Ipv4Addr::new(203, 0, 113, 8)];
+        client.ban("sshd", &ips).unwrap();
+        client.unban("sshd", &ips[..1]).unwrap();
+        assert!(matches!(
+            client.ban("unknown", &ips),
+            Err(IpBlockingError::CommandFailed { .. })
+        ));
+        assert_eq!(
+            fs::read_to_string(&log).unwrap(),
+            "set sshd banip 203.0.113.7 203.0.113.8\n\
+             set sshd unbanip 203.0.113.7\n\
+             set unknown banip 203.0.113.7 203.0.113.8\n"
+        );
+    }
+}
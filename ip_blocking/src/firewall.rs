@@ -0,0 +1,450 @@
+use serde::Deserialize;
+use std::{
+    fmt, fs,
+    io::Write,
+    net::Ipv4Addr,
+    path::{Path, PathBuf},
+    process::Command,
+    str::FromStr,
+    time::Duration,
+};
+
+use crate::IpBlockingError;
+
+const DEFAULT_NAME: &str = "service_observer";
+const DEFAULT_HOSTS_DENY: &str = "/etc/hosts.deny";
+/// Name of the nftables set inside the table of the firewall
+const NFT_SET: &str = "blocked";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlockBackend {
+    #[default]
+    Nftables,
+    /// An ipset matched by a single iptables rule
+    Ipset,
+    Ufw,
+    HostsDeny,
+}
+
+impl BlockBackend {
+    /// nftables and ipset drop the entries themselves once their timeout passed,
+    /// the other backends need the ban to be lifted
+    pub fn expires_natively(&self) -> bool {
+        matches!(self, Self::Nftables | Self::Ipset)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Nftables => "nftables",
+            Self::Ipset => "ipset",
+            Self::Ufw => "ufw",
+            Self::HostsDeny => "hosts_deny",
+        }
+    }
+}
+
+impl FromStr for BlockBackend {
+    type Err = ();
+
+    fn from_str(backend: &str) -> Result<Self, Self::Err> {
+        match backend {
+            "nftables" => Ok(Self::Nftables),
+            "ipset" => Ok(Self::Ipset),
+            "ufw" => Ok(Self::Ufw),
+            "hosts_deny" => Ok(Self::HostsDeny),
+            _ => Err(()),
+        }
+    }
+}
+
+impl fmt::Display for BlockBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Single step of a ban, shown as shell command in the dry-run preview
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    Command(Vec<String>),
+    /// Runs the command only if the check fails, keeps rules from being added twice
+    CommandUnless {
+        check: Vec<String>,
+        command: Vec<String>,
+    },
+    AppendLines {
+        path: PathBuf,
+        lines: Vec<String>,
+    },
+    RemoveLines {
+        path: PathBuf,
+        lines: Vec<String>,
+    },
+}
+
+impl Action {
+    pub fn apply(&self) -> Result<(), IpBlockingError> {
+        match self {
+            Self::Command(command) => run(command),
+            Self::CommandUnless { check, command } => match run(check) {
+                Ok(()) => Ok(()),
+                Err(IpBlockingError::CommandFailed { .. }) => run(command),
+                Err(err) => Err(err),
+            },
+            Self::AppendLines { path, lines } => append_lines(path, lines),
+            Self::RemoveLines { path, lines } => remove_lines(path, lines),
+        }
+    }
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Command(command) => write!(f, "{}", shell_words(command)),
+            Self::CommandUnless { check, command } => {
+                write!(f, "{} || {}", shell_words(check), shell_words(command))
+            }
+            Self::AppendLines { path, lines } => {
+                let lines: Vec<String> = lines
+                    .iter()
+                    .map(|line| format!("echo {} >> {}", quote(line), path.display()))
+                    .collect();
+                f.write_str(&lines.join("\n"))
+            }
+            Self::RemoveLines { path, lines } => {
+                let lines: Vec<String> = lines
+                    .iter()
+                    .map(|line| {
+                        format!(
+                            "sed -i {} {}",
+                            quote(&format!("\\|^{}$|d", line)),
+                            path.display()
+                        )
+                    })
+                    .collect();
+                f.write_str(&lines.join("\n"))
+            }
+        }
+    }
+}
+
+/// Turns ips into the rules of a single firewall backend
+#[derive(Debug, Clone)]
+pub struct Firewall {
+    backend: BlockBackend,
+    /// nftables table, ipset set and marker of the hosts.deny entries
+    name: String,
+    hosts_deny: PathBuf,
+}
+
+impl Firewall {
+    pub fn new(backend: BlockBackend) -> Self {
+        Self {
+            backend,
+            name: DEFAULT_NAME.to_owned(),
+            hosts_deny: PathBuf::from(DEFAULT_HOSTS_DENY),
+        }
+    }
+
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = name.to_owned();
+        self
+    }
+
+    pub fn with_hosts_deny<P: AsRef<Path>>(mut self, hosts_deny: P) -> Self {
+        self.hosts_deny = hosts_deny.as_ref().to_owned();
+        self
+    }
+
+    pub fn backend(&self) -> BlockBackend {
+        self.backend
+    }
+
+    /// Actions that ban the ips, backends with native expiry lift them after the duration
+    pub fn ban_actions(&self, ips: &[Ipv4Addr], duration: Duration) -> Vec<Action> {
+        if ips.is_empty() {
+            return Vec::new();
+        }
+        let name = self.name.as_str();
+        let timeout = duration.as_secs().max(1);
+        match self.backend {
+            BlockBackend::Nftables => {
+                let elements: Vec<String> = ips
+                    .iter()
+                    .map(|ip| format!("{} timeout {}s", ip, timeout))
+                    .collect();
+                vec![
+                    command(&["nft", "add", "table", "inet", name]),
+                    command(&[
+                        "nft",
+                        "add",
+                        "set",
+                        "inet",
+                        name,
+                        NFT_SET,
+                        "{ type ipv4_addr; flags timeout; }",
+                    ]),
+                    command(&[
+                        "nft",
+                        "add",
+                        "chain",
+                        "inet",
+                        name,
+                        "input",
+                        "{ type filter hook input priority -10; policy accept; }",
+                    ]),
+                    // Re-adding the rule on every ban would duplicate it
+                    command(&["nft", "flush", "chain", "inet", name, "input"]),
+                    command(&[
+                        "nft",
+                        "add",
+                        "rule",
+                        "inet",
+                        name,
+                        "input",
+                        "ip",
+                        "saddr",
+                        &format!("@{}", NFT_SET),
+                        "drop",
+                    ]),
+                    command(&[
+                        "nft",
+                        "add",
+                        "element",
+                        "inet",
+                        name,
+                        NFT_SET,
+                        &format!("{{ {} }}", elements.join(", ")),
+                    ]),
+                ]
+            }
+            BlockBackend::Ipset => {
+                let rule = [
+                    "INPUT",
+                    "-m",
+                    "set",
+                    "--match-set",
+                    name,
+                    "src",
+                    "-j",
+                    "DROP",
+                ];
+                let mut actions = vec![
+                    command(&["ipset", "create", name, "hash:ip", "timeout", "0", "-exist"]),
+                    Action::CommandUnless {
+                        check: words(&["iptables", "-C"], &rule),
+                        command: words(&["iptables", "-I"], &rule),
+                    },
+                ];
+                actions.extend(ips.iter().map(|ip| {
+                    command(&[
+                        "ipset",
+                        "add",
+                        name,
+                        &ip.to_string(),
+                        "timeout",
+                        &timeout.to_string(),
+                        "-exist",
+                    ])
+                }));
+                actions
+            }
+            BlockBackend::Ufw => ips
+                .iter()
+                .map(|ip| command(&["ufw", "prepend", "deny", "from", &ip.to_string()]))
+                .collect(),
+            BlockBackend::HostsDeny => vec![Action::AppendLines {
+                path: self.hosts_deny.clone(),
+                lines: self.hosts_deny_lines(ips),
+            }],
+        }
+    }
+
+    pub fn unban_actions(&self, ips: &[Ipv4Addr]) -> Vec<Action> {
+        if ips.is_empty() {
+            return Vec::new();
+        }
+        let name = self.name.as_str();
+        match self.backend {
+            BlockBackend::Nftables => {
+                let elements: Vec<String> = ips.iter().map(Ipv4Addr::to_string).collect();
+                vec![command(&[
+                    "nft",
+                    "delete",
+                    "element",
+                    "inet",
+                    name,
+                    NFT_SET,
+                    &format!("{{ {} }}", elements.join(", ")),
+                ])]
+            }
+            BlockBackend::Ipset => ips
+                .iter()
+                .map(|ip| command(&["ipset", "del", name, &ip.to_string(), "-exist"]))
+                .collect(),
+            BlockBackend::Ufw => ips
+                .iter()
+                .map(|ip| command(&["ufw", "delete", "deny", "from", &ip.to_string()]))
+                .collect(),
+            BlockBackend::HostsDeny => vec![Action::RemoveLines {
+                path: self.hosts_deny.clone(),
+                lines: self.hosts_deny_lines(ips),
+            }],
+        }
+    }
+
+    /// Applies the actions in order and stops at the first failure
+    pub fn apply(&self, actions: &[Action]) -> Result<(), IpBlockingError> {
+        actions.iter().try_for_each(Action::apply)
+    }
+
+    fn hosts_deny_lines(&self, ips: &[Ipv4Addr]) -> Vec<String> {
+        ips.iter()
+            .map(|ip| format!("sshd: {} # {}", ip, self.name))
+            .collect()
+    }
+}
+
+fn command(words: &[&str]) -> Action {
+    Action::Command(words.iter().map(|word| (*word).to_owned()).collect())
+}
+
+fn words(program: &[&str], args: &[&str]) -> Vec<String> {
+    program
+        .iter()
+        .chain(args)
+        .map(|word| (*word).to_owned())
+        .collect()
+}
+
+fn run(command: &[String]) -> Result<(), IpBlockingError> {
+    let Some((program, args)) = command.split_first() else {
+        return Ok(());
+    };
+    let output = Command::new(program).args(args).output()?;
+    if output.status.success() {
+        return Ok(());
+    }
+    Err(IpBlockingError::CommandFailed {
+        command: shell_words(command),
+        stderr: String::from_utf8_lossy(&output.stderr).trim().to_owned(),
+    })
+}
+
+fn append_lines(path: &Path, lines: &[String]) -> Result<(), IpBlockingError> {
+    let content = fs::read_to_string(path).unwrap_or_default();
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    for line in lines {
+        if !content.lines().any(|saved_line| saved_line == line) {
+            writeln!(file, "{}", line)?;
+        }
+    }
+    Ok(())
+}
+
+fn remove_lines(path: &Path, lines: &[String]) -> Result<(), IpBlockingError> {
+    let content = fs::read_to_string(path)?;
+    let kept: String = content
+        .lines()
+        .filter(|saved_line| !lines.iter().any(|line| line == saved_line))
+        .map(|saved_line| format!("{}\n", saved_line))
+        .collect();
+    fs::write(path, kept)?;
+    Ok(())
+}
+
+fn shell_words(words: &[String]) -> String {
+    words
+        .iter()
+        .map(|word| quote(word))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn quote(word: &str) -> String {
+    if word
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || "-_.:/@".contains(c))
+    {
+        word.to_owned()
+    } else {
+        format!("'{}'", word.replace('\'', r"'\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IPS: [Ipv4Addr; 2] = [
+        Ipv4Addr::new(203, 0, 113, 7),
+        Ipv4Addr::new(198, 51, 100, 23),
+    ];
+
+    #[test]
+    fn should_preview_nftables_set() {
+        let actions =
+            Firewall::new(BlockBackend::Nftables).ban_actions(&IPS, Duration::from_secs(3600));
+        let preview: Vec<String> = actions.iter().map(ToString::to_string).collect();
+        assert_eq!(preview[0], "nft add table inet service_observer");
+        assert_eq!(
+            preview.last().unwrap(),
+            "nft add element inet service_observer blocked \
+             '{ 203.0.113.7 timeout 3600s, 198.51.100.23 timeout 3600s }'"
+        );
+    }
+
+    #[test]
+    fn should_check_for_the_iptables_rule() {
+        let actions = Firewall::new(BlockBackend::Ipset)
+            .with_name("observer")
+            .ban_actions(&IPS[..1], Duration::from_secs(60));
+        assert_eq!(
+            actions[1].to_string(),
+            "iptables -C INPUT -m set --match-set observer src -j DROP \
+             || iptables -I INPUT -m set --match-set observer src -j DROP"
+        );
+        assert_eq!(
+            actions[2].to_string(),
+            "ipset add observer 203.0.113.7 timeout 60 -exist"
+        );
+    }
+
+    #[test]
+    fn should_ban_and_unban_in_hosts_deny() {
+        // Unique per process so concurrent test runs don't share the file
+        let path =
+            std::env::temp_dir().join(format!("ip_blocking_hosts_deny_{}", std::process::id()));
+        fs::write(&path, "ALL: 192.0.2.1\n").unwrap();
+        let firewall = Firewall::new(BlockBackend::HostsDeny).with_hosts_deny(&path);
+        let ban = firewall.ban_actions(&IPS, Duration::from_secs(60));
+        firewall.apply(&ban).unwrap();
+        // Applying twice keeps a single entry per ip
+        firewall.apply(&ban).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "ALL: 192.0.2.1\n\
+             sshd: 203.0.113.7 # service_observer\n\
+             sshd: 198.51.100.23 # service_observer\n"
+        );
+        firewall.apply(&firewall.unban_actions(&IPS[..1])).unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "ALL: 192.0.2.1\nsshd: 198.51.100.23 # service_observer\n"
+        );
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn should_report_failed_commands() {
+        let action = Action::Command(vec!["false".to_owned()]);
+        assert!(matches!(
+            action.apply(),
+            Err(IpBlockingError::CommandFailed { .. })
+        ));
+    }
+}
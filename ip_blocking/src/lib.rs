@@ -0,0 +1,9 @@
+mod error;
+pub mod fail2ban;
+pub mod firewall;
+pub mod policy;
+
+pub use error::IpBlockingError;
+pub use fail2ban::{Fail2banBan, Fail2banClient, Fail2banDb};
+pub use firewall::{Action, BlockBackend, Firewall};
+pub use policy::BlockPolicy;
@@ -0,0 +1,63 @@
+use std::net::Ipv4Addr;
+
+/// Picks the ips to ban from their amount of detections
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockPolicy {
+    /// Bans at most this many ips, the ones with the most detections first
+    pub top: Option<usize>,
+    /// Bans only ips with at least this many detections
+    pub min_detections: usize,
+}
+
+impl Default for BlockPolicy {
+    fn default() -> Self {
+        Self {
+            top: Some(10),
+            min_detections: 1,
+        }
+    }
+}
+
+impl BlockPolicy {
+    pub fn offenders(&self, detections: &[(Ipv4Addr, usize)]) -> Vec<Ipv4Addr> {
+        let mut offenders: Vec<(Ipv4Addr, usize)> = detections
+            .iter()
+            .filter(|(_, amount)| *amount >= self.min_detections)
+            .copied()
+            .collect();
+        offenders.sort_by(|(_, amount_1), (_, amount_2)| amount_2.cmp(amount_1));
+        offenders
+            .into_iter()
+            .take(self.top.unwrap_or(usize::MAX))
+            .map(|(ip, _)| ip)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_pick_top_offenders_above_threshold() {
+        let detections = [
+            (Ipv4Addr::new(203, 0, 113, 1), 4),
+            (Ipv4Addr::new(203, 0, 113, 2), 40),
+            (Ipv4Addr::new(203, 0, 113, 3), 12),
+            (Ipv4Addr::new(203, 0, 113, 4), 25),
+        ];
+        let policy = BlockPolicy {
+            top: Some(2),
+            min_detections: 5,
+        };
+        assert_eq!(
+            policy.offenders(&detections),
+            vec![Ipv4Addr::new(203, 0, 113, 2), Ipv4Addr::new(203, 0, 113, 4)]
+        );
+        let policy = BlockPolicy {
+            top: None,
+            ..policy
+        };
+        assert_eq!(policy.offenders(&detections).len(), 3);
+    }
+}
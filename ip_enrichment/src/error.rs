@@ -0,0 +1,9 @@
+#[derive(thiserror::Error, Debug)]
+pub enum IpEnrichmentError {
+    #[error("Request failed")]
+    RequestError,
+    #[error("Response parsing failed")]
+    ResponseParsingError(#[from] serde_json::Error),
+    #[error("Reverse DNS lookup failed")]
+    ResolveError(#[from] hickory_resolver::error::ResolveError),
+}
@@ -0,0 +1,11 @@
+mod error;
+pub mod model;
+pub mod rdap;
+pub mod reverse_dns;
+pub mod scanner;
+
+pub use error::IpEnrichmentError;
+pub use model::{Enrichment, Registration};
+pub use rdap::RdapClient;
+pub use reverse_dns::ReverseDnsResolver;
+pub use scanner::EnrichmentScanner;
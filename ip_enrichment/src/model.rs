@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct Registration {
+    pub network_name: Option<String>,
+    pub cidr: Option<String>,
+    pub abuse_email: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct Enrichment {
+    pub ptr: Option<String>,
+    pub registration: Registration,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct RdapNetwork {
+    pub name: Option<String>,
+    #[serde(rename = "startAddress")]
+    pub start_address: Option<String>,
+    #[serde(rename = "endAddress")]
+    pub end_address: Option<String>,
+    #[serde(default)]
+    pub cidr0_cidrs: Vec<RdapCidr>,
+    #[serde(default)]
+    pub entities: Vec<RdapEntity>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct RdapCidr {
+    pub v4prefix: Option<String>,
+    pub length: u8,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct RdapEntity {
+    #[serde(default)]
+    pub roles: Vec<String>,
+    #[serde(rename = "vcardArray")]
+    pub vcard_array: Option<serde_json::Value>,
+    #[serde(default)]
+    pub entities: Vec<RdapEntity>,
+}
+
+impl RdapEntity {
+    /// Depth first search for the email of the first entity with the `abuse` role
+    pub(crate) fn abuse_email(&self) -> Option<String> {
+        if self.roles.iter().any(|role| role == "abuse") {
+            if let Some(email) = self.email() {
+                return Some(email);
+            }
+        }
+        self.entities.iter().find_map(RdapEntity::abuse_email)
+    }
+
+    /// jCard layout: `["vcard", [["email", {}, "text", "abuse@example.com"], ...]]`
+    fn email(&self) -> Option<String> {
+        self.vcard_array
+            .as_ref()?
+            .get(1)?
+            .as_array()?
+            .iter()
+            .find(|property| property.get(0).and_then(|name| name.as_str()) == Some("email"))?
+            .get(3)?
+            .as_str()
+            .map(str::to_owned)
+    }
+}
+
+impl From<RdapNetwork> for Registration {
+    fn from(network: RdapNetwork) -> Self {
+        let cidr = network
+            .cidr0_cidrs
+            .iter()
+            .find_map(|cidr| {
+                cidr.v4prefix
+                    .as_ref()
+                    .map(|prefix| format!("{}/{}", prefix, cidr.length))
+            })
+            .or_else(|| match (&network.start_address, &network.end_address) {
+                (Some(start), Some(end)) => Some(format!("{} - {}", start, end)),
+                _ => None,
+            });
+        Self {
+            abuse_email: network.entities.iter().find_map(RdapEntity::abuse_email),
+            network_name: network.name,
+            cidr,
+        }
+    }
+}
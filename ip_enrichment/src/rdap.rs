@@ -0,0 +1,101 @@
+use std::net::Ipv4Addr;
+
+use crate::{model::RdapNetwork, IpEnrichmentError, Registration};
+
+/// Bootstrap service that redirects to the responsible regional registry
+const DEFAULT_RDAP_URL: &str = "https://rdap.org";
+
+#[derive(Debug, Clone)]
+pub struct RdapClient {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl<'a> RdapClient {
+    pub fn new(base_url: Option<&'a str>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url: base_url
+                .unwrap_or(DEFAULT_RDAP_URL)
+                .trim_end_matches('/')
+                .to_owned(),
+        }
+    }
+
+    pub async fn registration(&self, ipv4: &Ipv4Addr) -> Result<Registration, IpEnrichmentError> {
+        self.client
+            .get(format!("{}/ip/{}", self.base_url, ipv4))
+            .header("Accept", "application/rdap+json")
+            .send()
+            .await
+            .map_err(|_| IpEnrichmentError::RequestError)?
+            .text()
+            .await
+            .map_err(|_| IpEnrichmentError::RequestError)
+            .and_then(|str_body| {
+                let ret = serde_json::from_str::<RdapNetwork>(&str_body)?;
+                Ok(ret.into())
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::*;
+
+    const RDAP_RESPONSE: &str = r#"{
+        "objectClassName": "ip network",
+        "name": "EXAMPLE-NET",
+        "startAddress": "192.0.2.0",
+        "endAddress": "192.0.2.255",
+        "cidr0_cidrs": [{ "v4prefix": "192.0.2.0", "length": 24 }],
+        "entities": [{
+            "roles": ["registrant"],
+            "entities": [{
+                "roles": ["abuse"],
+                "vcardArray": ["vcard", [
+                    ["version", {}, "text", "4.0"],
+                    ["email", {}, "text", "abuse@example.net"]
+                ]]
+            }]
+        }]
+    }"#;
+
+    /// Serves a single canned RDAP response on a random local port
+    async fn stub_rdap_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = [0; 1024];
+            let _ = stream.read(&mut request).await.unwrap();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/rdap+json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                RDAP_RESPONSE.len(),
+                RDAP_RESPONSE
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+        });
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn should_parse_registration() {
+        let base_url = stub_rdap_server().await;
+        let registration = RdapClient::new(Some(&base_url))
+            .registration(&Ipv4Addr::new(192, 0, 2, 10))
+            .await
+            .unwrap();
+        assert_eq!(registration.network_name.as_deref(), Some("EXAMPLE-NET"));
+        assert_eq!(registration.cidr.as_deref(), Some("192.0.2.0/24"));
+        assert_eq!(
+            registration.abuse_email.as_deref(),
+            Some("abuse@example.net")
+        );
+    }
+}
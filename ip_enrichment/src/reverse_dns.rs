@@ -0,0 +1,102 @@
+use hickory_resolver::{
+    config::{NameServerConfigGroup, ResolverConfig, ResolverOpts},
+    error::ResolveErrorKind,
+    TokioAsyncResolver,
+};
+use std::net::{Ipv4Addr, SocketAddr};
+
+use crate::IpEnrichmentError;
+
+#[derive(Clone)]
+pub struct ReverseDnsResolver {
+    resolver: TokioAsyncResolver,
+}
+
+impl ReverseDnsResolver {
+    /// Uses the given nameserver or falls back to the system configuration
+    pub fn new(nameserver: Option<SocketAddr>) -> Result<Self, IpEnrichmentError> {
+        let resolver = match nameserver {
+            Some(nameserver) => {
+                let name_servers = NameServerConfigGroup::from_ips_clear(
+                    &[nameserver.ip()],
+                    nameserver.port(),
+                    true,
+                );
+                TokioAsyncResolver::tokio(
+                    ResolverConfig::from_parts(None, vec![], name_servers),
+                    ResolverOpts::default(),
+                )
+            }
+            None => TokioAsyncResolver::tokio_from_system_conf()?,
+        };
+        Ok(Self { resolver })
+    }
+
+    pub async fn ptr(&self, ipv4: &Ipv4Addr) -> Result<Option<String>, IpEnrichmentError> {
+        match self.resolver.reverse_lookup((*ipv4).into()).await {
+            Ok(lookup) => Ok(lookup
+                .iter()
+                .next()
+                .map(|name| name.to_utf8().trim_end_matches('.').to_owned())),
+            Err(err) => match err.kind() {
+                ResolveErrorKind::NoRecordsFound { .. } => Ok(None),
+                _ => Err(err.into()),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hickory_resolver::proto::{
+        op::{Message, MessageType},
+        rr::{rdata::PTR, Name, RData, Record},
+        serialize::binary::BinEncodable,
+    };
+    use tokio::net::UdpSocket;
+
+    use super::*;
+
+    const PTR_NAME: &str = "scanner.example.net.";
+
+    /// Answers a single PTR query on a random local port
+    async fn stub_dns_server() -> SocketAddr {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let addr = socket.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut buf = [0; 512];
+            let (len, peer) = socket.recv_from(&mut buf).await.unwrap();
+            let request = Message::from_vec(&buf[..len]).unwrap();
+            let query = request.queries()[0].clone();
+            let mut response = Message::new();
+            response
+                .set_id(request.id())
+                .set_message_type(MessageType::Response)
+                .set_op_code(request.op_code())
+                .set_recursion_desired(true)
+                .set_recursion_available(true)
+                .add_query(query.clone())
+                .add_answer(Record::from_rdata(
+                    query.name().clone(),
+                    300,
+                    RData::PTR(PTR(Name::from_utf8(PTR_NAME).unwrap())),
+                ));
+            socket
+                .send_to(&response.to_bytes().unwrap(), peer)
+                .await
+                .unwrap();
+        });
+        addr
+    }
+
+    #[tokio::test]
+    async fn should_resolve_ptr_with_configured_nameserver() {
+        let nameserver = stub_dns_server().await;
+        let ptr = ReverseDnsResolver::new(Some(nameserver))
+            .unwrap()
+            .ptr(&Ipv4Addr::new(192, 0, 2, 10))
+            .await
+            .unwrap();
+        assert_eq!(ptr.as_deref(), Some("scanner.example.net"));
+    }
+}
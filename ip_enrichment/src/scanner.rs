@@ -0,0 +1,27 @@
+use std::net::{Ipv4Addr, SocketAddr};
+
+use crate::{Enrichment, IpEnrichmentError, RdapClient, ReverseDnsResolver};
+
+#[derive(Clone)]
+pub struct EnrichmentScanner {
+    resolver: ReverseDnsResolver,
+    rdap_client: RdapClient,
+}
+
+impl<'a> EnrichmentScanner {
+    pub fn new(
+        nameserver: Option<SocketAddr>,
+        rdap_url: Option<&'a str>,
+    ) -> Result<Self, IpEnrichmentError> {
+        Ok(Self {
+            resolver: ReverseDnsResolver::new(nameserver)?,
+            rdap_client: RdapClient::new(rdap_url),
+        })
+    }
+
+    pub async fn ip_enrichment(&self, ipv4: &Ipv4Addr) -> Result<Enrichment, IpEnrichmentError> {
+        let ptr = self.resolver.ptr(ipv4).await?;
+        let registration = self.rdap_client.registration(ipv4).await?;
+        Ok(Enrichment { ptr, registration })
+    }
+}
@@ -4,4 +4,6 @@ pub enum IpScannerError {
     RequestError,
     #[error("Response parsing failed")]
     ResponseParsingError(#[from] serde_json::Error),
+    #[error("No data available: {0}")]
+    NoData(String),
 }
@@ -1,7 +1,9 @@
 mod error;
 pub mod model;
+pub mod provider;
 pub mod scanner;
 
 pub use error::IpScannerError;
 pub use model::Geolocation;
+pub use provider::GeolocationProvider;
 pub use scanner::IpScanner;
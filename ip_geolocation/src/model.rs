@@ -5,4 +5,12 @@ pub struct Geolocation {
     pub latitude: f64,
     pub longitude: f64,
     pub country_name: String,
+    pub org: Option<String>,
+    pub asn: Option<String>,
+}
+
+/// Body returned by shodan if the request could not be answered
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct ErrorResponse {
+    pub error: String,
 }
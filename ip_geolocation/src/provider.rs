@@ -0,0 +1,21 @@
+use async_trait::async_trait;
+use std::net::Ipv4Addr;
+
+use crate::{Geolocation, IpScannerError};
+
+#[async_trait]
+pub trait GeolocationProvider {
+    async fn ip_geolocation(&self, ipv4: &Ipv4Addr) -> Result<Geolocation, IpScannerError>;
+
+    /// Looks up one ip after another, providers with a bulk endpoint should override this
+    async fn ip_geolocations(
+        &self,
+        ips: &[Ipv4Addr],
+    ) -> Vec<(Ipv4Addr, Result<Geolocation, IpScannerError>)> {
+        let mut geolocations = Vec::with_capacity(ips.len());
+        for ipv4 in ips {
+            geolocations.push((*ipv4, self.ip_geolocation(ipv4).await));
+        }
+        geolocations
+    }
+}
@@ -1,8 +1,9 @@
+use async_trait::async_trait;
 use std::net::Ipv4Addr;
 
 use threaty::api::shodan::{shodan_api::ShodanAPI, shodan_client::ShodanClient};
 
-use crate::{Geolocation, IpScannerError};
+use crate::{model::ErrorResponse, Geolocation, GeolocationProvider, IpScannerError};
 
 #[derive(Debug, Clone)]
 pub struct IpScanner {
@@ -15,8 +16,12 @@ impl<'a> IpScanner {
             shodan_client: ShodanClient::new(shodan_key, None, None),
         }
     }
+}
 
-    pub async fn ip_geolocation(&self, ipv4: &Ipv4Addr) -> Result<Geolocation, IpScannerError> {
+/// Shodan has no bulk host endpoint, so batches use the default one by one lookup
+#[async_trait]
+impl GeolocationProvider for IpScanner {
+    async fn ip_geolocation(&self, ipv4: &Ipv4Addr) -> Result<Geolocation, IpScannerError> {
         self.shodan_client
             .clone()
             .host_info(ipv4.to_owned().into(), None, None)
@@ -27,6 +32,9 @@ impl<'a> IpScanner {
             .await
             .map_err(|_| IpScannerError::RequestError)
             .and_then(|str_body| {
+                if let Ok(ErrorResponse { error }) = serde_json::from_str(&str_body) {
+                    return Err(IpScannerError::NoData(error));
+                }
                 let ret = serde_json::from_str::<Geolocation>(&str_body)?;
                 Ok(ret)
             })
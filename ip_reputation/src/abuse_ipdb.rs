@@ -0,0 +1,42 @@
+use std::net::Ipv4Addr;
+
+use crate::{model::AbuseResponse, AbuseReport, IpReputationError};
+
+const ABUSE_IPDB_CHECK_URL: &str = "https://api.abuseipdb.com/api/v2/check";
+const MAX_AGE_IN_DAYS: &str = "90";
+
+#[derive(Debug, Clone)]
+pub struct AbuseIpDbClient {
+    client: reqwest::Client,
+    api_key: String,
+}
+
+impl<'a> AbuseIpDbClient {
+    pub fn new(api_key: &'a str) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            api_key: api_key.to_owned(),
+        }
+    }
+
+    pub async fn check(&self, ipv4: &Ipv4Addr) -> Result<AbuseReport, IpReputationError> {
+        self.client
+            .get(ABUSE_IPDB_CHECK_URL)
+            .query(&[
+                ("ipAddress", ipv4.to_string().as_str()),
+                ("maxAgeInDays", MAX_AGE_IN_DAYS),
+            ])
+            .header("Key", &self.api_key)
+            .header("Accept", "application/json")
+            .send()
+            .await
+            .map_err(|_| IpReputationError::RequestError)?
+            .text()
+            .await
+            .map_err(|_| IpReputationError::RequestError)
+            .and_then(|str_body| {
+                let ret = serde_json::from_str::<AbuseResponse>(&str_body)?;
+                Ok(ret.data)
+            })
+    }
+}
@@ -0,0 +1,95 @@
+use ipnet::Ipv4Net;
+use std::{fs, net::Ipv4Addr, path::Path};
+
+use crate::IpReputationError;
+
+/// In-memory copy of a public blocklist, e.g. Spamhaus DROP, FireHOL level1 or the Tor exit list.
+#[derive(Debug, Clone)]
+pub struct Blocklist {
+    pub name: String,
+    networks: Vec<Ipv4Net>,
+}
+
+impl Blocklist {
+    pub fn from_file<P: AsRef<Path>>(name: &str, path: P) -> Result<Self, IpReputationError> {
+        let content = fs::read_to_string(path)?;
+        Ok(Self::parse(name, &content))
+    }
+
+    /// Understands plain ip/cidr lists with `#` or `;` comments (Spamhaus DROP,
+    /// FireHOL netsets, Tor bulk exit list) and Tor's `ExitAddress` format.
+    pub fn parse(name: &str, content: &str) -> Self {
+        let networks = content
+            .lines()
+            .filter_map(|line| {
+                let line = line.split(['#', ';']).next().unwrap_or_default();
+                let mut tokens = line.split_whitespace();
+                let entry = match tokens.next()? {
+                    "ExitAddress" => tokens.next()?,
+                    entry => entry,
+                };
+                entry
+                    .parse::<Ipv4Net>()
+                    .or_else(|_| entry.parse::<Ipv4Addr>().map(Ipv4Net::from))
+                    .ok()
+            })
+            .collect();
+        Self {
+            name: name.to_owned(),
+            networks,
+        }
+    }
+
+    pub fn contains(&self, ipv4: &Ipv4Addr) -> bool {
+        self.networks.iter().any(|network| network.contains(ipv4))
+    }
+
+    pub fn len(&self) -> usize {
+        self.networks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.networks.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_spamhaus_drop() {
+        let list = Blocklist::parse(
+            "spamhaus_drop",
+            "; Spamhaus DROP List\n1.10.16.0/20 ; SBL256894\n1.19.0.0/16 ; SBL434604\n",
+        );
+        assert_eq!(list.len(), 2);
+        assert!(list.contains(&Ipv4Addr::new(1, 10, 20, 1)));
+        assert!(!list.contains(&Ipv4Addr::new(1, 20, 0, 1)));
+    }
+
+    #[test]
+    fn should_parse_firehol_netset() {
+        let list = Blocklist::parse(
+            "firehol_level1",
+            "#\n# firehol_level1\n#\n0.0.0.0/8\n5.188.10.0/23\n31.184.198.71\n",
+        );
+        assert_eq!(list.len(), 3);
+        assert!(list.contains(&Ipv4Addr::new(31, 184, 198, 71)));
+        assert!(list.contains(&Ipv4Addr::new(5, 188, 11, 200)));
+    }
+
+    #[test]
+    fn should_parse_tor_exit_addresses() {
+        let list = Blocklist::parse(
+            "tor_exit",
+            "ExitNode 0011BD2485AD45D984EC4159C88FC066E5E3300E\n\
+             Published 2023-12-14 22:45:24\n\
+             ExitAddress 162.247.74.201 2023-12-15 13:01:47\n\
+             185.220.101.1\n",
+        );
+        assert_eq!(list.len(), 2);
+        assert!(list.contains(&Ipv4Addr::new(162, 247, 74, 201)));
+        assert!(list.contains(&Ipv4Addr::new(185, 220, 101, 1)));
+    }
+}
@@ -0,0 +1,9 @@
+#[derive(thiserror::Error, Debug)]
+pub enum IpReputationError {
+    #[error("Request failed")]
+    RequestError,
+    #[error("Response parsing failed")]
+    ResponseParsingError(#[from] serde_json::Error),
+    #[error("Failed to read blocklist")]
+    BlocklistRead(#[from] std::io::Error),
+}
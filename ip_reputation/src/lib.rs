@@ -0,0 +1,11 @@
+pub mod abuse_ipdb;
+pub mod blocklist;
+mod error;
+pub mod model;
+pub mod scanner;
+
+pub use abuse_ipdb::AbuseIpDbClient;
+pub use blocklist::Blocklist;
+pub use error::IpReputationError;
+pub use model::{AbuseReport, Reputation};
+pub use scanner::ReputationScanner;
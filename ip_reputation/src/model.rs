@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AbuseReport {
+    pub abuse_confidence_score: i32,
+    pub total_reports: i32,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub(crate) struct AbuseResponse {
+    pub data: AbuseReport,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct Reputation {
+    /// Only available if an AbuseIPDB key is configured
+    pub abuse: Option<AbuseReport>,
+    /// Names of the local blocklists that contain the ip
+    pub blocklists: Vec<String>,
+}
@@ -0,0 +1,61 @@
+use std::net::Ipv4Addr;
+
+use crate::{AbuseIpDbClient, Blocklist, IpReputationError, Reputation};
+
+#[derive(Debug, Clone, Default)]
+pub struct ReputationScanner {
+    abuse_ipdb: Option<AbuseIpDbClient>,
+    blocklists: Vec<Blocklist>,
+}
+
+impl ReputationScanner {
+    pub fn new(abuse_ipdb_key: Option<&str>, blocklists: Vec<Blocklist>) -> Self {
+        Self {
+            abuse_ipdb: abuse_ipdb_key.map(AbuseIpDbClient::new),
+            blocklists,
+        }
+    }
+
+    pub async fn ip_reputation(&self, ipv4: &Ipv4Addr) -> Result<Reputation, IpReputationError> {
+        let abuse = match &self.abuse_ipdb {
+            Some(client) => Some(client.check(ipv4).await?),
+            None => None,
+        };
+        let blocklists = self.blocklists_containing(ipv4);
+        Ok(Reputation { abuse, blocklists })
+    }
+
+    /// Names of the loaded blocklists which contain the ip
+    pub fn blocklists_containing(&self, ipv4: &Ipv4Addr) -> Vec<String> {
+        self.blocklists
+            .iter()
+            .filter(|list| list.contains(ipv4))
+            .map(|list| list.name.to_owned())
+            .collect()
+    }
+
+    pub fn checks_abuse(&self) -> bool {
+        self.abuse_ipdb.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_list_containing_blocklists() {
+        let scanner = ReputationScanner::new(
+            None,
+            vec![
+                Blocklist::parse("drop", "203.0.113.0/24 ; SBL1\n"),
+                Blocklist::parse("tor_exit", "198.51.100.7\n"),
+            ],
+        );
+        assert_eq!(
+            scanner.blocklists_containing(&Ipv4Addr::new(203, 0, 113, 7)),
+            vec!["drop".to_owned()]
+        );
+        assert!(!scanner.checks_abuse());
+    }
+}
@@ -8,4 +8,7 @@ pub enum JournalError {
     OutputParsing(#[from] FromUtf8Error),
     #[error("Failed to execute the journal command")]
     Exec(#[from] std::io::Error),
+    /// Stderr of journalctl, e.g. for an invalid time range
+    #[error("The journal command failed: {0}")]
+    Command(String),
 }
@@ -1,12 +1,16 @@
-use std::process::{Command, Stdio};
+use std::{
+    io::{BufRead, BufReader, Lines},
+    process::{Child, ChildStdout, Command, Stdio},
+    sync::{Arc, Mutex},
+};
 
 use crate::{
     error::JournalError,
-    models::{LogOutput, Parser},
+    models::{LogEntry, LogOutput, Parser},
 };
 
 pub struct Journal<'a> {
-    service: &'a str,
+    services: Vec<&'a str>,
     format: &'a str,
     order: Option<&'static str>,
     no_pager: Option<&'static str>,
@@ -14,12 +18,18 @@ pub struct Journal<'a> {
     quiet: Option<&'static str>,
     since: Option<&'a str>,
     until: Option<&'a str>,
+    after_cursor: Option<&'a str>,
 }
 
 impl<'a> Journal<'a> {
     pub fn with_service(service: &'a str) -> JournalBuilder<'a> {
+        Self::with_services(&[service])
+    }
+
+    /// Reads the entries of all services merged by time
+    pub fn with_services(services: &[&'a str]) -> JournalBuilder<'a> {
         JournalBuilder {
-            service,
+            services: services.to_vec(),
             format: "json",
             order: None,
             no_pager: None,
@@ -27,17 +37,48 @@ impl<'a> Journal<'a> {
             quiet: None,
             since: None,
             until: None,
+            after_cursor: None,
         }
     }
 
     pub fn read(&self) -> Result<LogOutput, JournalError> {
+        let output = self
+            .command()
+            .spawn()
+            .and_then(|child| child.wait_with_output())?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(JournalError::Command(stderr.trim().to_owned()));
+        }
+        LogOutput::parse(String::from_utf8(output.stdout)?)
+    }
+
+    /// Keeps reading, yields the log entries as soon as they are written to the journal
+    pub fn follow(&self) -> Result<JournalFollow, JournalError> {
+        let mut child = self.command().arg("--follow").spawn()?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| std::io::Error::other("journalctl has no stdout"))?;
+        Ok(JournalFollow {
+            handle: FollowHandle {
+                child: Arc::new(Mutex::new(child)),
+            },
+            lines: BufReader::new(stdout).lines(),
+        })
+    }
+
+    fn command(&self) -> Command {
         let mut command = Command::new("journalctl");
         command
             .env("LC_ALL", "en_US.UTF-8")
             .stdout(Stdio::piped())
             .stdin(Stdio::piped())
             .stderr(Stdio::piped());
-        command.args(["-u", self.service, "-o", self.format]);
+        for service in &self.services {
+            command.args(["-u", service]);
+        }
+        command.args(["-o", self.format]);
         if let Some(no_pager) = self.no_pager {
             command.arg(no_pager);
         };
@@ -56,13 +97,63 @@ impl<'a> Journal<'a> {
         if let Some(until) = self.until {
             command.args(["-U", until]);
         };
-        let output = command.spawn().and_then(|child| child.wait_with_output())?;
-        LogOutput::parse(String::from_utf8(output.stdout)?)
+        if let Some(cursor) = self.after_cursor {
+            command.args(["--after-cursor", cursor]);
+        };
+        command
+    }
+}
+
+/// Log entries of a followed journal, stops the journalctl process when dropped
+pub struct JournalFollow {
+    handle: FollowHandle,
+    lines: Lines<BufReader<ChildStdout>>,
+}
+
+impl JournalFollow {
+    /// Handle to stop the journal while another thread waits for its next entry
+    pub fn handle(&self) -> FollowHandle {
+        self.handle.clone()
+    }
+}
+
+/// Stops the journalctl process of a [`JournalFollow`], which then yields no more entries
+#[derive(Debug, Clone)]
+pub struct FollowHandle {
+    child: Arc<Mutex<Child>>,
+}
+
+impl FollowHandle {
+    pub fn stop(&self) {
+        if let Ok(mut child) = self.child.lock() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+impl Iterator for JournalFollow {
+    type Item = Result<LogEntry, JournalError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.lines.next()? {
+                Ok(line) if line.trim().is_empty() => continue,
+                Ok(line) => return Some(LogEntry::parse(line)),
+                Err(err) => return Some(Err(err.into())),
+            }
+        }
+    }
+}
+
+impl Drop for JournalFollow {
+    fn drop(&mut self) {
+        self.handle.stop();
     }
 }
 
 pub struct JournalBuilder<'a> {
-    service: &'a str,
+    services: Vec<&'a str>,
     format: &'a str,
     lines: Option<usize>,
     no_pager: Option<&'static str>,
@@ -70,6 +161,7 @@ pub struct JournalBuilder<'a> {
     quiet: Option<&'static str>,
     since: Option<&'a str>,
     until: Option<&'a str>,
+    after_cursor: Option<&'a str>,
 }
 
 impl<'a> JournalBuilder<'a> {
@@ -108,9 +200,15 @@ impl<'a> JournalBuilder<'a> {
         self
     }
 
+    /// Only entries after the one with this cursor
+    pub fn after_cursor(mut self, cursor: &'a str) -> Self {
+        self.after_cursor = Some(cursor);
+        self
+    }
+
     pub fn build(self) -> Journal<'a> {
         Journal {
-            service: self.service,
+            services: self.services,
             order: self.order,
             no_pager: self.no_pager,
             format: self.format,
@@ -118,6 +216,7 @@ impl<'a> JournalBuilder<'a> {
             quiet: self.quiet,
             since: self.since,
             until: self.until,
+            after_cursor: self.after_cursor,
         }
     }
 }
@@ -142,6 +241,31 @@ mod tests {
         assert!(log_output.is_ok(), "Failed to parse logs")
     }
 
+    #[test]
+    fn should_fail_for_invalid_since() {
+        let output = Journal::with_service(LOG_SERVICE)
+            .since("not a date")
+            .no_pager()
+            .build()
+            .read();
+        assert!(
+            matches!(output, Err(JournalError::Command(stderr)) if !stderr.is_empty()),
+            "Should return the error of journalctl"
+        );
+    }
+
+    #[test]
+    fn should_stop_followed_journal() {
+        let mut entries = Journal::with_service(LOG_SERVICE)
+            .lines(0)
+            .no_pager()
+            .build()
+            .follow()
+            .unwrap();
+        entries.handle().stop();
+        assert!(entries.next().is_none(), "Should end once stopped");
+    }
+
     #[test]
     fn should_read_lines_amount() {
         let output = Journal::with_service(LOG_SERVICE)
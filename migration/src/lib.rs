@@ -1,12 +1,144 @@
 pub use sea_orm_migration::prelude::*;
 
 mod m20221209_103858_base;
+mod m20231218_091512_reputation;
+mod m20231219_143027_ip_enrichment;
+mod m20231220_180412_geolocation_provider;
+mod m20231221_102245_lookup_failure;
+mod m20231222_094518_ssh_event;
+mod m20231223_081530_ip_daily_activity;
+mod m20231224_110342_normalize_datetimes;
+mod m20231227_143910_ip_annotation;
+mod m20231229_101530_ban;
 
 pub struct Migrator;
 
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20221209_103858_base::Migration)]
+        vec![
+            Box::new(m20221209_103858_base::Migration),
+            Box::new(m20231218_091512_reputation::Migration),
+            Box::new(m20231219_143027_ip_enrichment::Migration),
+            Box::new(m20231220_180412_geolocation_provider::Migration),
+            Box::new(m20231221_102245_lookup_failure::Migration),
+            Box::new(m20231222_094518_ssh_event::Migration),
+            Box::new(m20231223_081530_ip_daily_activity::Migration),
+            Box::new(m20231224_110342_normalize_datetimes::Migration),
+            Box::new(m20231227_143910_ip_annotation::Migration),
+            Box::new(m20231229_101530_ban::Migration),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm_migration::sea_orm::{
+        ConnectionTrait, Database, DatabaseConnection, QueryResult, Statement,
+    };
+
+    const TABLES: [&str; 9] = [
+        "ip",
+        "geolocation",
+        "reputation",
+        "lookup_failure",
+        "ssh_event",
+        "ip_activity",
+        "ip_daily_activity",
+        "ip_annotation",
+        "ban",
+    ];
+
+    /// Amount of migrations before the datetime normalization
+    const MIGRATIONS_BEFORE_NORMALIZATION: u32 = 7;
+
+    async fn connect() -> DatabaseConnection {
+        Database::connect("sqlite::memory:")
+            .await
+            .expect("To open in-memory database")
+    }
+
+    async fn applied(db: &DatabaseConnection) -> usize {
+        Migrator::get_applied_migrations(db).await.unwrap().len()
+    }
+
+    async fn has_table(db: &DatabaseConnection, table: &str) -> bool {
+        SchemaManager::new(db).has_table(table).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn should_migrate_up_down_up() {
+        let db = connect().await;
+        Migrator::up(&db, None).await.unwrap();
+        assert_eq!(applied(&db).await, Migrator::migrations().len());
+        for table in TABLES {
+            assert!(has_table(&db, table).await, "{table} should exist");
+        }
+
+        Migrator::down(&db, None).await.unwrap();
+        assert_eq!(applied(&db).await, 0);
+        for table in TABLES {
+            assert!(!has_table(&db, table).await, "{table} should be dropped");
+        }
+
+        Migrator::up(&db, None).await.unwrap();
+        assert_eq!(applied(&db).await, Migrator::migrations().len());
+    }
+
+    #[tokio::test]
+    async fn should_roll_back_every_migration() {
+        let db = connect().await;
+        for applied_before in 0..Migrator::migrations().len() {
+            Migrator::up(&db, Some(1)).await.unwrap();
+            Migrator::down(&db, Some(1)).await.unwrap();
+            assert_eq!(applied(&db).await, applied_before);
+            Migrator::up(&db, Some(1)).await.unwrap();
+            assert_eq!(applied(&db).await, applied_before + 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn should_normalize_sqlite_datetimes() {
+        let db = connect().await;
+        Migrator::up(&db, Some(MIGRATIONS_BEFORE_NORMALIZATION))
+            .await
+            .unwrap();
+        db.execute_unprepared(
+            "INSERT INTO ip (ipv4, created, updated) \
+             VALUES ('203.0.113.7', '2023-12-09T10:38:58Z', 'yesterday')",
+        )
+        .await
+        .unwrap();
+        db.execute_unprepared(
+            "INSERT INTO geolocation (ip_id, latitude, longitude, created) VALUES (1, 0, 0, '')",
+        )
+        .await
+        .unwrap();
+
+        Migrator::up(&db, None).await.unwrap();
+        let query = |sql: &str| {
+            db.query_one(Statement::from_string(
+                db.get_database_backend(),
+                sql.to_owned(),
+            ))
+        };
+        let ip: QueryResult = query("SELECT created, updated FROM ip")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            ip.try_get::<String>("", "created").unwrap(),
+            "2023-12-09 10:38:58"
+        );
+        assert_eq!(ip.try_get::<Option<String>>("", "updated").unwrap(), None);
+        let geolocation: QueryResult = query("SELECT created FROM geolocation")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            geolocation.try_get::<String>("", "created").unwrap(),
+            "1970-01-01 00:00:00"
+        );
     }
 }
@@ -12,12 +12,9 @@ impl MigrationTrait for Migration {
     }
 
     async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
-        manager.drop_table(ip_table::drop()).await?;
-        manager.drop_index(ip_table::drop_index()).await?;
+        // Index and foreign key are dropped along with their tables
         manager.drop_table(geolocation_table::drop()).await?;
-        manager
-            .drop_foreign_key(geolocation_table::drop_fk())
-            .await?;
+        manager.drop_table(ip_table::drop()).await?;
         Ok(())
     }
 }
@@ -62,13 +59,6 @@ mod ip_table {
     pub(crate) fn drop() -> TableDropStatement {
         Table::drop().if_exists().table(Ip::Table).to_owned()
     }
-
-    pub(crate) fn drop_index() -> IndexDropStatement {
-        Index::drop()
-            .table(Ip::Table)
-            .name(IP_INDEX_NAME)
-            .to_owned()
-    }
 }
 
 mod geolocation_table {
@@ -122,11 +112,4 @@ mod geolocation_table {
             .table(Geolocation::Table)
             .to_owned()
     }
-
-    pub(crate) fn drop_fk() -> ForeignKeyDropStatement {
-        ForeignKey::drop()
-            .table(Geolocation::Table)
-            .name(IP_FK_NAME)
-            .to_owned()
-    }
 }
@@ -0,0 +1,82 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.create_table(reputation_table::create()).await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.drop_table(reputation_table::drop()).await?;
+        Ok(())
+    }
+}
+
+mod reputation_table {
+    use sea_orm_migration::prelude::*;
+
+    const IP_FK_NAME: &str = "fk-reputation-ip_id";
+
+    #[derive(Iden)]
+    enum Ip {
+        Table,
+        Id,
+    }
+
+    #[derive(Iden)]
+    enum Reputation {
+        Table,
+        Id,
+        IpId,
+        AbuseConfidenceScore,
+        AbuseTotalReports,
+        Blocklists,
+        Created,
+    }
+
+    pub(crate) fn create() -> TableCreateStatement {
+        Table::create()
+            .table(Reputation::Table)
+            .if_not_exists()
+            .col(
+                ColumnDef::new(Reputation::Id)
+                    .integer()
+                    .not_null()
+                    .auto_increment()
+                    .primary_key(),
+            )
+            .col(ColumnDef::new(Reputation::AbuseConfidenceScore).integer())
+            .col(ColumnDef::new(Reputation::AbuseTotalReports).integer())
+            .col(
+                ColumnDef::new(Reputation::Blocklists)
+                    .string()
+                    .not_null()
+                    .default(""),
+            )
+            .col(
+                ColumnDef::new(Reputation::Created)
+                    .date_time()
+                    .not_null()
+                    .extra("DEFAULT CURRENT_TIMESTAMP".to_owned()),
+            )
+            .col(ColumnDef::new(Reputation::IpId).integer().not_null())
+            .foreign_key(
+                ForeignKey::create()
+                    .name(IP_FK_NAME)
+                    .from(Reputation::Table, Reputation::IpId)
+                    .to(Ip::Table, Ip::Id),
+            )
+            .to_owned()
+    }
+
+    pub(crate) fn drop() -> TableDropStatement {
+        Table::drop()
+            .if_exists()
+            .table(Reputation::Table)
+            .to_owned()
+    }
+}
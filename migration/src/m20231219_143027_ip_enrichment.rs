@@ -0,0 +1,76 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Sqlite only supports a single alter option per statement
+        for statement in ip_table::add_columns() {
+            manager.alter_table(statement).await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for statement in ip_table::drop_columns() {
+            manager.alter_table(statement).await?;
+        }
+        Ok(())
+    }
+}
+
+mod ip_table {
+    use sea_orm_migration::prelude::*;
+
+    #[derive(Iden, Clone, Copy)]
+    enum Ip {
+        Table,
+        Ptr,
+        RdapNetworkName,
+        RdapCidr,
+        RdapAbuseEmail,
+        Enriched,
+    }
+
+    fn columns() -> Vec<ColumnDef> {
+        vec![
+            ColumnDef::new(Ip::Ptr).string().to_owned(),
+            ColumnDef::new(Ip::RdapNetworkName).string().to_owned(),
+            ColumnDef::new(Ip::RdapCidr).string().to_owned(),
+            ColumnDef::new(Ip::RdapAbuseEmail).string().to_owned(),
+            ColumnDef::new(Ip::Enriched).date_time().to_owned(),
+        ]
+    }
+
+    pub(crate) fn add_columns() -> Vec<TableAlterStatement> {
+        columns()
+            .into_iter()
+            .map(|mut column| {
+                Table::alter()
+                    .table(Ip::Table)
+                    .add_column(&mut column)
+                    .to_owned()
+            })
+            .collect()
+    }
+
+    pub(crate) fn drop_columns() -> Vec<TableAlterStatement> {
+        [
+            Ip::Ptr,
+            Ip::RdapNetworkName,
+            Ip::RdapCidr,
+            Ip::RdapAbuseEmail,
+            Ip::Enriched,
+        ]
+        .into_iter()
+        .map(|column| {
+            Table::alter()
+                .table(Ip::Table)
+                .drop_column(column)
+                .to_owned()
+        })
+        .collect()
+    }
+}
@@ -0,0 +1,61 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Sqlite only supports a single alter option per statement
+        for statement in geolocation_table::add_columns() {
+            manager.alter_table(statement).await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for statement in geolocation_table::drop_columns() {
+            manager.alter_table(statement).await?;
+        }
+        Ok(())
+    }
+}
+
+mod geolocation_table {
+    use sea_orm_migration::prelude::*;
+
+    #[derive(Iden, Clone, Copy)]
+    enum Geolocation {
+        Table,
+        CountryName,
+        Org,
+        Asn,
+    }
+
+    const COLUMNS: [Geolocation; 3] =
+        [Geolocation::CountryName, Geolocation::Org, Geolocation::Asn];
+
+    pub(crate) fn add_columns() -> Vec<TableAlterStatement> {
+        COLUMNS
+            .into_iter()
+            .map(|column| {
+                Table::alter()
+                    .table(Geolocation::Table)
+                    .add_column(ColumnDef::new(column).string())
+                    .to_owned()
+            })
+            .collect()
+    }
+
+    pub(crate) fn drop_columns() -> Vec<TableAlterStatement> {
+        COLUMNS
+            .into_iter()
+            .map(|column| {
+                Table::alter()
+                    .table(Geolocation::Table)
+                    .drop_column(column)
+                    .to_owned()
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,93 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.create_table(lookup_failure_table::create()).await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.drop_table(lookup_failure_table::drop()).await?;
+        Ok(())
+    }
+}
+
+mod lookup_failure_table {
+    use sea_orm_migration::prelude::*;
+
+    const IP_FK_NAME: &str = "fk-lookup_failure-ip_id";
+
+    #[derive(Iden)]
+    enum Ip {
+        Table,
+        Id,
+    }
+
+    #[derive(Iden)]
+    enum LookupFailure {
+        Table,
+        Id,
+        IpId,
+        Reason,
+        Attempts,
+        NextRetry,
+        Created,
+        Updated,
+    }
+
+    pub(crate) fn create() -> TableCreateStatement {
+        Table::create()
+            .table(LookupFailure::Table)
+            .if_not_exists()
+            .col(
+                ColumnDef::new(LookupFailure::Id)
+                    .integer()
+                    .not_null()
+                    .auto_increment()
+                    .primary_key(),
+            )
+            .col(
+                ColumnDef::new(LookupFailure::IpId)
+                    .integer()
+                    .not_null()
+                    .unique_key(),
+            )
+            .col(ColumnDef::new(LookupFailure::Reason).string().not_null())
+            .col(
+                ColumnDef::new(LookupFailure::Attempts)
+                    .integer()
+                    .not_null()
+                    .default(1),
+            )
+            .col(
+                ColumnDef::new(LookupFailure::NextRetry)
+                    .date_time()
+                    .not_null(),
+            )
+            .col(
+                ColumnDef::new(LookupFailure::Created)
+                    .date_time()
+                    .not_null()
+                    .extra("DEFAULT CURRENT_TIMESTAMP".to_owned()),
+            )
+            .col(ColumnDef::new(LookupFailure::Updated).date_time())
+            .foreign_key(
+                ForeignKey::create()
+                    .name(IP_FK_NAME)
+                    .from(LookupFailure::Table, LookupFailure::IpId)
+                    .to(Ip::Table, Ip::Id),
+            )
+            .to_owned()
+    }
+
+    pub(crate) fn drop() -> TableDropStatement {
+        Table::drop()
+            .if_exists()
+            .table(LookupFailure::Table)
+            .to_owned()
+    }
+}
@@ -0,0 +1,167 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.create_table(ssh_event_table::create()).await?;
+        manager
+            .create_index(ssh_event_table::create_index())
+            .await?;
+        manager.create_table(ip_activity_table::create()).await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.drop_table(ip_activity_table::drop()).await?;
+        manager.drop_table(ssh_event_table::drop()).await?;
+        Ok(())
+    }
+}
+
+#[derive(Iden)]
+enum Ip {
+    Table,
+    Id,
+}
+
+mod ssh_event_table {
+    use super::Ip;
+    use sea_orm_migration::prelude::*;
+
+    const IP_FK_NAME: &str = "fk-ssh_event-ip_id";
+    const IP_TIMESTAMP_INDEX_NAME: &str = "idx-ssh_event-ip_id-timestamp";
+
+    #[derive(Iden)]
+    enum SshEvent {
+        Table,
+        Id,
+        IpId,
+        Timestamp,
+        User,
+        Port,
+        EventType,
+        Message,
+        Cursor,
+        Created,
+    }
+
+    pub(crate) fn create() -> TableCreateStatement {
+        Table::create()
+            .table(SshEvent::Table)
+            .if_not_exists()
+            .col(
+                ColumnDef::new(SshEvent::Id)
+                    .integer()
+                    .not_null()
+                    .auto_increment()
+                    .primary_key(),
+            )
+            .col(ColumnDef::new(SshEvent::IpId).integer().not_null())
+            .col(ColumnDef::new(SshEvent::Timestamp).date_time().not_null())
+            .col(ColumnDef::new(SshEvent::User).string())
+            .col(ColumnDef::new(SshEvent::Port).integer())
+            .col(ColumnDef::new(SshEvent::EventType).string().not_null())
+            .col(ColumnDef::new(SshEvent::Message).string().not_null())
+            .col(
+                ColumnDef::new(SshEvent::Cursor)
+                    .string()
+                    .not_null()
+                    .unique_key(),
+            )
+            .col(
+                ColumnDef::new(SshEvent::Created)
+                    .date_time()
+                    .not_null()
+                    .extra("DEFAULT CURRENT_TIMESTAMP".to_owned()),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name(IP_FK_NAME)
+                    .from(SshEvent::Table, SshEvent::IpId)
+                    .to(Ip::Table, Ip::Id),
+            )
+            .to_owned()
+    }
+
+    pub(crate) fn create_index() -> IndexCreateStatement {
+        Index::create()
+            .if_not_exists()
+            .name(IP_TIMESTAMP_INDEX_NAME)
+            .table(SshEvent::Table)
+            .col(SshEvent::IpId)
+            .col(SshEvent::Timestamp)
+            .to_owned()
+    }
+
+    pub(crate) fn drop() -> TableDropStatement {
+        Table::drop().if_exists().table(SshEvent::Table).to_owned()
+    }
+}
+
+mod ip_activity_table {
+    use super::Ip;
+    use sea_orm_migration::prelude::*;
+
+    const IP_FK_NAME: &str = "fk-ip_activity-ip_id";
+
+    #[derive(Iden)]
+    enum IpActivity {
+        Table,
+        Id,
+        IpId,
+        FirstSeen,
+        LastSeen,
+        TotalAttempts,
+        Updated,
+    }
+
+    pub(crate) fn create() -> TableCreateStatement {
+        Table::create()
+            .table(IpActivity::Table)
+            .if_not_exists()
+            .col(
+                ColumnDef::new(IpActivity::Id)
+                    .integer()
+                    .not_null()
+                    .auto_increment()
+                    .primary_key(),
+            )
+            .col(
+                ColumnDef::new(IpActivity::IpId)
+                    .integer()
+                    .not_null()
+                    .unique_key(),
+            )
+            .col(ColumnDef::new(IpActivity::FirstSeen).date_time().not_null())
+            .col(ColumnDef::new(IpActivity::LastSeen).date_time().not_null())
+            .col(
+                ColumnDef::new(IpActivity::TotalAttempts)
+                    .integer()
+                    .not_null()
+                    .default(0),
+            )
+            .col(
+                ColumnDef::new(IpActivity::Updated)
+                    .date_time()
+                    .not_null()
+                    .extra("DEFAULT CURRENT_TIMESTAMP".to_owned()),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name(IP_FK_NAME)
+                    .from(IpActivity::Table, IpActivity::IpId)
+                    .to(Ip::Table, Ip::Id),
+            )
+            .to_owned()
+    }
+
+    pub(crate) fn drop() -> TableDropStatement {
+        Table::drop()
+            .if_exists()
+            .table(IpActivity::Table)
+            .to_owned()
+    }
+}
@@ -0,0 +1,97 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(ip_daily_activity_table::create())
+            .await?;
+        manager
+            .create_index(ip_daily_activity_table::create_index())
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.drop_table(ip_daily_activity_table::drop()).await?;
+        Ok(())
+    }
+}
+
+mod ip_daily_activity_table {
+    use sea_orm_migration::prelude::*;
+
+    const IP_FK_NAME: &str = "fk-ip_daily_activity-ip_id";
+    const IP_DAY_INDEX_NAME: &str = "idx-ip_daily_activity-ip_id-day";
+
+    #[derive(Iden)]
+    enum Ip {
+        Table,
+        Id,
+    }
+
+    #[derive(Iden)]
+    enum IpDailyActivity {
+        Table,
+        Id,
+        IpId,
+        Day,
+        Events,
+        Attempts,
+    }
+
+    pub(crate) fn create() -> TableCreateStatement {
+        Table::create()
+            .table(IpDailyActivity::Table)
+            .if_not_exists()
+            .col(
+                ColumnDef::new(IpDailyActivity::Id)
+                    .integer()
+                    .not_null()
+                    .auto_increment()
+                    .primary_key(),
+            )
+            .col(ColumnDef::new(IpDailyActivity::IpId).integer().not_null())
+            .col(ColumnDef::new(IpDailyActivity::Day).date().not_null())
+            .col(
+                ColumnDef::new(IpDailyActivity::Events)
+                    .integer()
+                    .not_null()
+                    .default(0),
+            )
+            .col(
+                ColumnDef::new(IpDailyActivity::Attempts)
+                    .integer()
+                    .not_null()
+                    .default(0),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name(IP_FK_NAME)
+                    .from(IpDailyActivity::Table, IpDailyActivity::IpId)
+                    .to(Ip::Table, Ip::Id),
+            )
+            .to_owned()
+    }
+
+    pub(crate) fn create_index() -> IndexCreateStatement {
+        Index::create()
+            .if_not_exists()
+            .unique()
+            .name(IP_DAY_INDEX_NAME)
+            .table(IpDailyActivity::Table)
+            .col(IpDailyActivity::IpId)
+            .col(IpDailyActivity::Day)
+            .to_owned()
+    }
+
+    pub(crate) fn drop() -> TableDropStatement {
+        Table::drop()
+            .if_exists()
+            .table(IpDailyActivity::Table)
+            .to_owned()
+    }
+}
@@ -0,0 +1,59 @@
+use sea_orm_migration::prelude::*;
+use sea_orm_migration::sea_orm::DatabaseBackend;
+
+/// Datetime columns which sqlite stores as text, with whether they are nullable
+const DATETIME_COLUMNS: [(&str, &str, bool); 13] = [
+    ("ip", "created", false),
+    ("ip", "updated", true),
+    ("ip", "enriched", true),
+    ("geolocation", "created", false),
+    ("reputation", "created", false),
+    ("lookup_failure", "next_retry", false),
+    ("lookup_failure", "created", false),
+    ("lookup_failure", "updated", true),
+    ("ssh_event", "timestamp", false),
+    ("ssh_event", "created", false),
+    ("ip_activity", "first_seen", false),
+    ("ip_activity", "last_seen", false),
+    ("ip_activity", "updated", false),
+];
+
+/// Unparsable values of required columns fall back to the epoch, so they count as expired
+const FALLBACK_DATETIME: &str = "1970-01-01 00:00:00";
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Other backends store native datetime types already
+        if manager.get_database_backend() != DatabaseBackend::Sqlite {
+            return Ok(());
+        }
+        let db = manager.get_connection();
+        for (table, column, nullable) in DATETIME_COLUMNS {
+            let normalized = if nullable {
+                format!("datetime({column})")
+            } else {
+                format!("COALESCE(datetime({column}), '{FALLBACK_DATETIME}')")
+            };
+            db.execute_unprepared(&format!(
+                "UPDATE {table} SET {column} = {normalized} \
+                 WHERE {column} IS NOT NULL AND {column} IS NOT datetime({column})"
+            ))
+            .await?;
+        }
+        db.execute_unprepared(
+            "UPDATE ip_daily_activity SET day = COALESCE(date(day), '1970-01-01') \
+             WHERE day IS NOT date(day)",
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        // Normalized values stay readable by the previous schema
+        Ok(())
+    }
+}
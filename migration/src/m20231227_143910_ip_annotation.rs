@@ -0,0 +1,87 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.create_table(ip_annotation_table::create()).await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.drop_table(ip_annotation_table::drop()).await?;
+        Ok(())
+    }
+}
+
+mod ip_annotation_table {
+    use sea_orm_migration::prelude::*;
+
+    const IP_FK_NAME: &str = "fk-ip_annotation-ip_id";
+
+    #[derive(Iden)]
+    enum Ip {
+        Table,
+        Id,
+    }
+
+    #[derive(Iden)]
+    enum IpAnnotation {
+        Table,
+        Id,
+        IpId,
+        Tags,
+        Note,
+        Created,
+        Updated,
+    }
+
+    pub(crate) fn create() -> TableCreateStatement {
+        Table::create()
+            .table(IpAnnotation::Table)
+            .if_not_exists()
+            .col(
+                ColumnDef::new(IpAnnotation::Id)
+                    .integer()
+                    .not_null()
+                    .auto_increment()
+                    .primary_key(),
+            )
+            .col(
+                ColumnDef::new(IpAnnotation::IpId)
+                    .integer()
+                    .not_null()
+                    .unique_key(),
+            )
+            .col(
+                ColumnDef::new(IpAnnotation::Tags)
+                    .string()
+                    .not_null()
+                    .default(""),
+            )
+            .col(ColumnDef::new(IpAnnotation::Note).text())
+            .col(
+                ColumnDef::new(IpAnnotation::Created)
+                    .date_time()
+                    .not_null()
+                    .extra("DEFAULT CURRENT_TIMESTAMP".to_owned()),
+            )
+            .col(ColumnDef::new(IpAnnotation::Updated).date_time())
+            .foreign_key(
+                ForeignKey::create()
+                    .name(IP_FK_NAME)
+                    .from(IpAnnotation::Table, IpAnnotation::IpId)
+                    .to(Ip::Table, Ip::Id),
+            )
+            .to_owned()
+    }
+
+    pub(crate) fn drop() -> TableDropStatement {
+        Table::drop()
+            .if_exists()
+            .table(IpAnnotation::Table)
+            .to_owned()
+    }
+}
@@ -0,0 +1,72 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.create_table(ban_table::create()).await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager.drop_table(ban_table::drop()).await?;
+        Ok(())
+    }
+}
+
+mod ban_table {
+    use sea_orm_migration::prelude::*;
+
+    const IP_FK_NAME: &str = "fk-ban-ip_id";
+
+    #[derive(Iden)]
+    enum Ip {
+        Table,
+        Id,
+    }
+
+    #[derive(Iden)]
+    enum Ban {
+        Table,
+        Id,
+        IpId,
+        Backend,
+        Expires,
+        Created,
+    }
+
+    pub(crate) fn create() -> TableCreateStatement {
+        Table::create()
+            .table(Ban::Table)
+            .if_not_exists()
+            .col(
+                ColumnDef::new(Ban::Id)
+                    .integer()
+                    .not_null()
+                    .auto_increment()
+                    .primary_key(),
+            )
+            .col(ColumnDef::new(Ban::IpId).integer().not_null().unique_key())
+            .col(ColumnDef::new(Ban::Backend).string().not_null())
+            .col(ColumnDef::new(Ban::Expires).date_time().not_null())
+            .col(
+                ColumnDef::new(Ban::Created)
+                    .date_time()
+                    .not_null()
+                    .extra("DEFAULT CURRENT_TIMESTAMP".to_owned()),
+            )
+            .foreign_key(
+                ForeignKey::create()
+                    .name(IP_FK_NAME)
+                    .from(Ban::Table, Ban::IpId)
+                    .to(Ip::Table, Ip::Id),
+            )
+            .to_owned()
+    }
+
+    pub(crate) fn drop() -> TableDropStatement {
+        Table::drop().if_exists().table(Ban::Table).to_owned()
+    }
+}
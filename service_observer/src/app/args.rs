@@ -1,5 +1,84 @@
-use clap::Parser;
+use clap::{Parser, Subcommand, ValueEnum};
+use std::{net::Ipv4Addr, path::PathBuf};
 
 #[derive(Debug, Parser)]
 #[command(author, version, about, long_about = None)]
-pub(crate) struct Args {}
+pub(crate) struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    /// Config file, defaults to `config.toml` in the user config directory
+    #[arg(long, global = true)]
+    pub config: Option<PathBuf>,
+    /// Sqlite database file, overrides the configured database url
+    #[arg(long, global = true)]
+    pub database: Option<PathBuf>,
+    /// Systemd unit to read the logs of, repeat it for several units
+    #[arg(long = "unit", global = true)]
+    pub units: Vec<String>,
+    /// Read logs since this time, e.g. `yesterday`, `-2h` or `2023-12-24 18:00`
+    #[arg(long, global = true)]
+    pub since: Option<String>,
+    /// Read logs until this time, in the same format as `--since`
+    #[arg(long, global = true)]
+    pub until: Option<String>,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub(crate) enum Command {
+    /// Browse the logs in the terminal ui, the default
+    Tui,
+    /// Print a summary of the logs
+    Report {
+        /// Amount of ips and usernames listed
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+    },
+    /// Write the logs per ip
+    Export {
+        #[arg(long, value_enum, default_value_t = ExportFormat::Csv)]
+        format: ExportFormat,
+        /// Written to stdout without a file
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Geolocate a single ip
+    Lookup { ip: Ipv4Addr },
+    /// Maintain the database
+    Db {
+        #[command(subcommand)]
+        command: DbCommand,
+    },
+    /// Manage the config file
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub(crate) enum ExportFormat {
+    Csv,
+    Json,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub(crate) enum DbCommand {
+    /// Apply pending migrations
+    Migrate,
+    /// Remove data older than the configured retention
+    Prune,
+    /// Print the amount of saved rows
+    Stats,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub(crate) enum ConfigCommand {
+    /// Write a config file with the default settings
+    Init {
+        /// Replace an existing config file
+        #[arg(long)]
+        force: bool,
+    },
+    /// Validate the config file
+    Check,
+}
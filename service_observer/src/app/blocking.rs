@@ -0,0 +1,101 @@
+use ip_blocking::{Action, BlockBackend};
+use std::{net::Ipv4Addr, time::Duration};
+
+/// What a confirmed preview changes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum BlockTarget {
+    Firewall {
+        backend: BlockBackend,
+        duration: Duration,
+    },
+    Fail2banBan {
+        jail: String,
+    },
+    Fail2banUnban {
+        jails: Vec<String>,
+    },
+}
+
+/// Dry-run of a ban, nothing is applied until confirmed
+#[derive(Debug)]
+pub(crate) struct BlockPreview {
+    pub target: BlockTarget,
+    pub ips: Vec<Ipv4Addr>,
+    pub actions: Vec<Action>,
+    /// The confirmed actions run in the background
+    pub applying: bool,
+    /// Outcome of the apply, set once the actions ran
+    pub status: Option<Result<(), String>>,
+}
+
+impl BlockPreview {
+    pub fn new(target: BlockTarget, ips: Vec<Ipv4Addr>, actions: Vec<Action>) -> Self {
+        Self {
+            target,
+            ips,
+            actions,
+            applying: false,
+            status: None,
+        }
+    }
+
+    pub fn is_applied(&self) -> bool {
+        self.status.is_some()
+    }
+
+    /// Popup title of the dry-run
+    pub fn description(&self) -> String {
+        match &self.target {
+            BlockTarget::Firewall { backend, duration } => format!(
+                "ban {} ips via {} for {}",
+                self.ips.len(),
+                backend,
+                format_duration(*duration)
+            ),
+            BlockTarget::Fail2banBan { jail } => {
+                format!("ban {} ips in fail2ban jail {}", self.ips.len(), jail)
+            }
+            BlockTarget::Fail2banUnban { jails } => format!(
+                "unban {} ips in fail2ban jails {}",
+                self.ips.len(),
+                jails.join(", ")
+            ),
+        }
+    }
+
+    /// Lines shown in the popup, one per shell command
+    pub fn lines(&self) -> Vec<String> {
+        self.actions
+            .iter()
+            .flat_map(|action| {
+                action
+                    .to_string()
+                    .lines()
+                    .map(str::to_owned)
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+/// Whole hours, or minutes for bans shorter than an hour
+fn format_duration(duration: Duration) -> String {
+    let minutes = duration.as_secs() / 60;
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{}m", minutes),
+        (hours, 0) => format!("{}h", hours),
+        (hours, minutes) => format!("{}h {}m", hours, minutes),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_format_ban_durations() {
+        assert_eq!(format_duration(Duration::from_secs(30 * 60)), "30m");
+        assert_eq!(format_duration(Duration::from_secs(24 * 60 * 60)), "24h");
+        assert_eq!(format_duration(Duration::from_secs(90 * 60)), "1h 30m");
+    }
+}
@@ -1,3 +1,11 @@
 pub(crate) const APP_DRAW_TICK_RATE: u64 = 40;
 pub(crate) const TASK_TICK_RATE_MS: u64 = 250;
 pub(crate) const APP_NAME: &str = "service_observer";
+/// Ips with new logs within this time are highlighted until their detail pane was opened
+pub(crate) const RECENT_ACTIVITY_SECS: u64 = 5 * 60;
+/// Least time between two recalculations of the statistics over all logs
+pub(crate) const LOG_STATS_REFRESH_MS: u64 = 2000;
+pub(crate) const DASHBOARD_TAB: usize = 0;
+pub(crate) const TABLE_TAB: usize = 1;
+pub(crate) const MAP_TAB: usize = 2;
+pub(crate) const TIMELINE_TAB: usize = 3;
@@ -0,0 +1,134 @@
+use sshd_logs::{SshEvent, SshEventKind};
+use std::collections::{HashMap, HashSet};
+
+/// Entries in the top lists of the dashboard
+pub(crate) const TOP_AMOUNT: usize = 10;
+
+/// Figures of the analysed logs shown on the dashboard
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct LogSummary {
+    pub attempts: usize,
+    pub ips: usize,
+    pub usernames: usize,
+    pub logins: usize,
+    /// Most tried usernames with their amount of attempts
+    pub top_usernames: Vec<(String, usize)>,
+    /// Sorted microseconds since the unix epoch of every attempt
+    attempt_timestamps: Vec<i64>,
+}
+
+impl LogSummary {
+    pub fn from_events(events: &[SshEvent]) -> Self {
+        let attempts: Vec<&SshEvent> = events
+            .iter()
+            .filter(|event| event.kind.is_attempt())
+            .collect();
+        let mut attempt_timestamps: Vec<i64> =
+            attempts.iter().map(|event| event.timestamp).collect();
+        attempt_timestamps.sort_unstable();
+        let usernames: HashSet<&str> = events
+            .iter()
+            .filter_map(|event| event.user.as_deref())
+            .collect();
+        Self {
+            attempts: attempts.len(),
+            ips: events
+                .iter()
+                .map(|event| event.ipv4)
+                .collect::<HashSet<_>>()
+                .len(),
+            usernames: usernames.len(),
+            logins: attempts
+                .iter()
+                .filter(|event| event.kind == SshEventKind::Accepted)
+                .count(),
+            top_usernames: top_counts(
+                attempts
+                    .iter()
+                    .filter_map(|event| event.user.as_deref())
+                    .map(|user| (user.to_owned(), 1)),
+            ),
+            attempt_timestamps,
+        }
+    }
+
+    /// Attempts per minute within the last `minutes` before `now`, in microseconds
+    pub fn attack_rate(&self, now: i64, minutes: i64) -> f64 {
+        let since = now - minutes * 60 * 1_000_000;
+        let recent = self.attempt_timestamps.len()
+            - self
+                .attempt_timestamps
+                .partition_point(|timestamp| *timestamp < since);
+        recent as f64 / minutes as f64
+    }
+
+    /// Most attempts within a single minute
+    pub fn peak_rate(&self) -> usize {
+        let mut per_minute: HashMap<i64, usize> = HashMap::new();
+        for timestamp in &self.attempt_timestamps {
+            *per_minute
+                .entry(timestamp.div_euclid(60 * 1_000_000))
+                .or_default() += 1;
+        }
+        per_minute.into_values().max().unwrap_or_default()
+    }
+}
+
+/// Sums the amounts per key, returns the largest sums first
+pub(crate) fn top_counts<I>(amounts: I) -> Vec<(String, usize)>
+where
+    I: IntoIterator<Item = (String, usize)>,
+{
+    let mut sums: HashMap<String, usize> = HashMap::new();
+    for (key, amount) in amounts {
+        *sums.entry(key).or_default() += amount;
+    }
+    let mut sums: Vec<(String, usize)> = sums.into_iter().collect();
+    sums.sort_by(|(key_1, sum_1), (key_2, sum_2)| sum_2.cmp(sum_1).then_with(|| key_1.cmp(key_2)));
+    sums.truncate(TOP_AMOUNT);
+    sums
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    fn event(ip: u8, minute: i64, user: &str, kind: SshEventKind) -> SshEvent {
+        SshEvent {
+            ipv4: Ipv4Addr::new(203, 0, 113, ip),
+            timestamp: minute * 60 * 1_000_000,
+            user: Some(user.to_owned()),
+            port: None,
+            kind,
+            message: String::new(),
+            cursor: String::new(),
+        }
+    }
+
+    #[test]
+    fn should_summarize_logs() {
+        let events = [
+            event(1, 0, "root", SshEventKind::FailedPassword),
+            event(1, 0, "root", SshEventKind::FailedPassword),
+            event(2, 8, "admin", SshEventKind::InvalidUser),
+            event(2, 8, "admin", SshEventKind::FailedPassword),
+            event(3, 9, "deploy", SshEventKind::Accepted),
+        ];
+        let summary = LogSummary::from_events(&events);
+        assert_eq!(summary.attempts, 4);
+        assert_eq!(summary.ips, 3);
+        assert_eq!(summary.usernames, 3);
+        assert_eq!(summary.logins, 1);
+        assert_eq!(
+            summary.top_usernames,
+            vec![
+                ("root".to_owned(), 2),
+                ("admin".to_owned(), 1),
+                ("deploy".to_owned(), 1)
+            ]
+        );
+        assert_eq!(summary.attack_rate(10 * 60 * 1_000_000, 5), 0.4);
+        assert_eq!(summary.peak_rate(), 2);
+    }
+}
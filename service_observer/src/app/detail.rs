@@ -0,0 +1,88 @@
+use chrono::{DateTime, Local};
+use crawler::{ActivityRecord, GeolocationRecord, SshEvent};
+use journal_parser::models::LogEntry;
+use std::net::Ipv4Addr;
+
+/// Lines scrolled with page up and page down
+const PAGE_LINES: usize = 10;
+
+/// Log lines and saved data of the ip opened from the ssh table
+#[derive(Debug)]
+pub(crate) struct DetailView {
+    pub ip: Ipv4Addr,
+    /// First shown log line
+    pub scroll: usize,
+    /// Latest geolocation, loaded from the database after opening
+    pub geolocation: Option<GeolocationRecord>,
+    pub activity: Option<ActivityRecord>,
+    pub loaded: bool,
+}
+
+impl DetailView {
+    pub fn new(ip: Ipv4Addr) -> Self {
+        Self {
+            ip,
+            scroll: 0,
+            geolocation: None,
+            activity: None,
+            loaded: false,
+        }
+    }
+
+    pub fn scroll_down(&mut self, lines: usize) {
+        self.scroll = (self.scroll + 1).min(lines.saturating_sub(1));
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.scroll = self.scroll.saturating_sub(1);
+    }
+
+    pub fn page_down(&mut self, lines: usize) {
+        self.scroll = (self.scroll + PAGE_LINES).min(lines.saturating_sub(1));
+    }
+
+    pub fn page_up(&mut self) {
+        self.scroll = self.scroll.saturating_sub(PAGE_LINES);
+    }
+}
+
+/// Single log line of the detail view
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct DetailLine {
+    pub timestamp: String,
+    pub user: String,
+    pub kind: String,
+    pub message: String,
+}
+
+impl DetailLine {
+    pub fn from_log(log: &LogEntry) -> Self {
+        let event = SshEvent::from_log(log);
+        Self {
+            timestamp: format_timestamp(&log.realtime_timestamp),
+            user: event
+                .as_ref()
+                .and_then(|event| event.user.to_owned())
+                .unwrap_or_default(),
+            kind: event
+                .map(|event| event.kind.to_string())
+                .unwrap_or_default(),
+            message: log.message.to_owned(),
+        }
+    }
+}
+
+/// Formats journald's microseconds since the unix epoch in local time
+pub(crate) fn format_timestamp(micros: &str) -> String {
+    micros
+        .parse::<i64>()
+        .ok()
+        .and_then(|micros| DateTime::from_timestamp(micros.div_euclid(1_000_000), 0))
+        .map(|timestamp| {
+            timestamp
+                .with_timezone(&Local)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+        })
+        .unwrap_or_else(|| micros.to_owned())
+}
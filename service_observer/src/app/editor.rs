@@ -0,0 +1,62 @@
+use crawler::Annotation;
+use std::net::Ipv4Addr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AnnotationField {
+    Tags,
+    Note,
+}
+
+/// Popup state while tags and note of an ip are edited
+#[derive(Debug)]
+pub(crate) struct AnnotationEditor {
+    pub ip: Ipv4Addr,
+    pub tags: String,
+    pub note: String,
+    pub field: AnnotationField,
+}
+
+impl AnnotationEditor {
+    pub fn new(ip: Ipv4Addr, annotation: Option<&Annotation>) -> Self {
+        Self {
+            ip,
+            tags: annotation
+                .map(|annotation| annotation.tags.join(", "))
+                .unwrap_or_default(),
+            note: annotation
+                .and_then(|annotation| annotation.note.to_owned())
+                .unwrap_or_default(),
+            field: AnnotationField::Tags,
+        }
+    }
+
+    pub fn next_field(&mut self) {
+        self.field = match self.field {
+            AnnotationField::Tags => AnnotationField::Note,
+            AnnotationField::Note => AnnotationField::Tags,
+        };
+    }
+
+    pub fn push(&mut self, character: char) {
+        self.active_field().push(character);
+    }
+
+    pub fn pop(&mut self) {
+        self.active_field().pop();
+    }
+
+    pub fn annotation(&self) -> Annotation {
+        let note = self.note.trim();
+        Annotation {
+            tags: Annotation::parse_tags(&self.tags),
+            note: (!note.is_empty()).then(|| note.to_owned()),
+        }
+    }
+
+    fn active_field(&mut self) -> &mut String {
+        match self.field {
+            AnnotationField::Tags => &mut self.tags,
+            AnnotationField::Note => &mut self.note,
+        }
+    }
+}
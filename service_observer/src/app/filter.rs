@@ -0,0 +1,162 @@
+use crawler::GeolocationRecord;
+use ipnet::Ipv4Net;
+use journal_parser::models::LogEntry;
+use sshd_logs::SshEvent;
+use std::net::Ipv4Addr;
+
+/// Parsed `/` search, every term has to match.
+///
+/// Plain terms match an ip prefix, terms with a `/` a network. `country:`, `user:` and
+/// `event:` match case-insensitive parts of the country name, a username or an event kind,
+/// `min:` the least amount of authentication attempts
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct SearchFilter {
+    ip_prefixes: Vec<String>,
+    networks: Vec<Ipv4Net>,
+    countries: Vec<String>,
+    users: Vec<String>,
+    events: Vec<String>,
+    min_attempts: Option<usize>,
+}
+
+impl SearchFilter {
+    pub fn parse(query: &str) -> Result<Self, String> {
+        let mut filter = Self::default();
+        for term in query.split_whitespace() {
+            match term.split_once(':') {
+                Some(("country", value)) => filter.countries.push(value.to_lowercase()),
+                Some(("user", value)) => filter.users.push(value.to_lowercase()),
+                Some(("event", value)) => filter.events.push(value.to_lowercase()),
+                Some(("min", value)) => {
+                    let min = value
+                        .parse()
+                        .map_err(|_| format!("Invalid attempt count `{}`", value))?;
+                    filter.min_attempts = Some(min);
+                }
+                Some((key, _)) => return Err(format!("Unknown filter `{}`", key)),
+                None if term.contains('/') => {
+                    let network = term
+                        .parse()
+                        .map_err(|_| format!("Invalid network `{}`", term))?;
+                    filter.networks.push(network);
+                }
+                None => filter.ip_prefixes.push(term.to_owned()),
+            }
+        }
+        Ok(filter)
+    }
+
+    pub fn matches(
+        &self,
+        ip: &Ipv4Addr,
+        logs: &[LogEntry],
+        location: Option<&GeolocationRecord>,
+    ) -> bool {
+        let ip_str = ip.to_string();
+        if !self
+            .ip_prefixes
+            .iter()
+            .all(|prefix| ip_str.starts_with(prefix.as_str()))
+            || !self.networks.iter().all(|network| network.contains(ip))
+        {
+            return false;
+        }
+        if !self.countries.is_empty() {
+            let country = location
+                .and_then(|location| location.country_name.as_deref())
+                .unwrap_or_default()
+                .to_lowercase();
+            if !self.countries.iter().all(|term| country.contains(term)) {
+                return false;
+            }
+        }
+        if self.users.is_empty() && self.events.is_empty() && self.min_attempts.is_none() {
+            return true;
+        }
+        let events: Vec<SshEvent> = logs.iter().filter_map(SshEvent::from_log).collect();
+        self.matches_events(&events)
+    }
+
+    /// Whether the match of an ip can change once its location is known
+    pub fn has_country_terms(&self) -> bool {
+        !self.countries.is_empty()
+    }
+
+    fn matches_events(&self, events: &[SshEvent]) -> bool {
+        let any_event = |term: &String, field: fn(&SshEvent) -> Option<&str>| {
+            events
+                .iter()
+                .filter_map(field)
+                .any(|value| value.to_lowercase().contains(term.as_str()))
+        };
+        let attempts = events
+            .iter()
+            .filter(|event| event.kind.is_attempt())
+            .count();
+        self.users
+            .iter()
+            .all(|term| any_event(term, |event| event.user.as_deref()))
+            && self
+                .events
+                .iter()
+                .all(|term| any_event(term, |event| Some(event.kind.as_str())))
+            && self.min_attempts.unwrap_or_default() <= attempts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sshd_logs::SshEventKind;
+
+    fn event(user: &str, kind: SshEventKind) -> SshEvent {
+        SshEvent {
+            ipv4: Ipv4Addr::new(203, 0, 113, 7),
+            timestamp: 1_700_000_000_000_000,
+            user: Some(user.to_owned()),
+            port: Some(22),
+            kind,
+            message: String::new(),
+            cursor: String::new(),
+        }
+    }
+
+    #[test]
+    fn should_match_all_terms() {
+        let ip = Ipv4Addr::new(203, 0, 113, 7);
+        let location = GeolocationRecord {
+            latitude: 0.0,
+            longitude: 0.0,
+            country_name: Some("Netherlands".to_owned()),
+            org: None,
+            asn: None,
+            created: Default::default(),
+        };
+        let matches_ip = |query: &str| {
+            SearchFilter::parse(query)
+                .unwrap()
+                .matches(&ip, &[], Some(&location))
+        };
+        assert!(matches_ip(""));
+        assert!(matches_ip("203.0 203.0.113.0/24 country:nether"));
+        assert!(!matches_ip("198.51"));
+        assert!(!matches_ip("203.0.112.0/24"));
+        assert!(!matches_ip("country:germany"));
+
+        let events = [
+            event("root", SshEventKind::FailedPassword),
+            event("admin", SshEventKind::InvalidUser),
+            event("admin", SshEventKind::FailedPassword),
+        ];
+        let matches_events =
+            |query: &str| SearchFilter::parse(query).unwrap().matches_events(&events);
+        assert!(matches_events("user:ADMIN event:invalid min:2"));
+        assert!(!matches_events("user:guest"));
+        assert!(!matches_events("event:accepted"));
+        assert!(!matches_events("min:3"));
+
+        assert!(SearchFilter::parse("min:many").is_err());
+        assert!(SearchFilter::parse("port:22").is_err());
+        assert!(SearchFilter::parse("203.0.113.0/33").is_err());
+    }
+}
@@ -0,0 +1,186 @@
+use std::net::Ipv4Addr;
+
+const MAX_ZOOM: f64 = 32.0;
+/// Least zoom when focusing a single location
+const FOCUS_ZOOM: f64 = 8.0;
+/// Share of the shown width or height moved by one pan
+const PAN_STEP: f64 = 0.1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MapPreset {
+    World,
+    Europe,
+    NorthAmerica,
+    Asia,
+}
+
+impl MapPreset {
+    /// Center as (lng, lat) and zoom
+    fn view(&self) -> ((f64, f64), f64) {
+        match self {
+            Self::World => ((0.0, 0.0), 1.0),
+            Self::Europe => ((15.0, 52.0), 4.0),
+            Self::NorthAmerica => ((-100.0, 45.0), 3.0),
+            Self::Asia => ((95.0, 35.0), 3.0),
+        }
+    }
+}
+
+/// Shown part of the world map, the cursor is always in its center
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct MapView {
+    /// (lng, lat)
+    pub center: (f64, f64),
+    /// 1 shows the whole world
+    pub zoom: f64,
+}
+
+impl Default for MapView {
+    fn default() -> Self {
+        Self::from_preset(MapPreset::World)
+    }
+}
+
+impl MapView {
+    pub fn from_preset(preset: MapPreset) -> Self {
+        let (center, zoom) = preset.view();
+        let mut view = Self { center, zoom };
+        view.clamp();
+        view
+    }
+
+    pub fn x_bounds(&self) -> [f64; 2] {
+        let half_width = 180.0 / self.zoom;
+        [self.center.0 - half_width, self.center.0 + half_width]
+    }
+
+    pub fn y_bounds(&self) -> [f64; 2] {
+        let half_height = 90.0 / self.zoom;
+        [self.center.1 - half_height, self.center.1 + half_height]
+    }
+
+    pub fn zoom_in(&mut self) {
+        self.zoom = (self.zoom * 2.0).min(MAX_ZOOM);
+        self.clamp();
+    }
+
+    pub fn zoom_out(&mut self) {
+        self.zoom = (self.zoom / 2.0).max(1.0);
+        self.clamp();
+    }
+
+    /// Moves by steps of a tenth of the shown width and height
+    pub fn pan(&mut self, right: f64, up: f64) {
+        self.center.0 += right * PAN_STEP * 360.0 / self.zoom;
+        self.center.1 += up * PAN_STEP * 180.0 / self.zoom;
+        self.clamp();
+    }
+
+    /// Centers the location and zooms in on it
+    pub fn focus(&mut self, position: (f64, f64)) {
+        self.center = position;
+        self.zoom = self.zoom.max(FOCUS_ZOOM);
+        self.clamp();
+    }
+
+    pub fn contains(&self, (lng, lat): (f64, f64)) -> bool {
+        let [west, east] = self.x_bounds();
+        let [south, north] = self.y_bounds();
+        (west..=east).contains(&lng) && (south..=north).contains(&lat)
+    }
+
+    /// Keeps the shown part within the world
+    fn clamp(&mut self) {
+        let half_width = 180.0 / self.zoom;
+        let half_height = 90.0 / self.zoom;
+        self.center.0 = self.center.0.clamp(-180.0 + half_width, 180.0 - half_width);
+        self.center.1 = self.center.1.clamp(-90.0 + half_height, 90.0 - half_height);
+    }
+}
+
+/// Ips sharing a location on the map
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct MapMarker {
+    pub latitude: f64,
+    pub longitude: f64,
+    pub country: Option<String>,
+    /// Ips with their authentication attempts, the most attempts first
+    pub ips: Vec<(Ipv4Addr, usize)>,
+}
+
+impl MapMarker {
+    pub fn attempts(&self) -> usize {
+        self.ips.iter().map(|(_, attempts)| attempts).sum()
+    }
+
+    /// 0 for few attempts up to 2 for many
+    pub fn intensity(&self) -> usize {
+        match self.attempts() {
+            0..=9 => 0,
+            10..=99 => 1,
+            _ => 2,
+        }
+    }
+}
+
+/// Marker closest to the center of the view, markers outside of the view are ignored
+pub(crate) fn nearest_marker<'a>(
+    markers: &'a [MapMarker],
+    view: &MapView,
+) -> Option<&'a MapMarker> {
+    let distance = |marker: &MapMarker| {
+        (marker.longitude - view.center.0).powi(2) + (marker.latitude - view.center.1).powi(2)
+    };
+    markers
+        .iter()
+        .filter(|marker| view.contains((marker.longitude, marker.latitude)))
+        .min_by(|marker_1, marker_2| distance(marker_1).total_cmp(&distance(marker_2)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn marker(longitude: f64, latitude: f64, attempts: usize) -> MapMarker {
+        MapMarker {
+            latitude,
+            longitude,
+            country: None,
+            ips: vec![(Ipv4Addr::new(203, 0, 113, 7), attempts)],
+        }
+    }
+
+    #[test]
+    fn should_keep_view_within_world() {
+        let mut view = MapView::default();
+        view.pan(1.0, 1.0);
+        assert_eq!(view, MapView::default());
+        view.zoom_in();
+        view.pan(-10.0, 0.0);
+        assert_eq!(view.x_bounds(), [-180.0, 0.0]);
+        view.zoom_out();
+        view.zoom_out();
+        assert_eq!(view.zoom, 1.0);
+    }
+
+    #[test]
+    fn should_snap_to_nearest_marker_in_view() {
+        let view = MapView::from_preset(MapPreset::Europe);
+        let markers = [
+            marker(-74.0, 40.7, 500),
+            marker(2.3, 48.9, 5),
+            marker(13.4, 52.5, 50),
+        ];
+        let nearest = nearest_marker(&markers, &view).unwrap();
+        assert_eq!(nearest.longitude, 13.4);
+        assert_eq!(nearest.intensity(), 1);
+        let view = MapView {
+            center: (-74.0, 40.0),
+            zoom: 32.0,
+        };
+        assert_eq!(nearest_marker(&markers[1..], &view), None);
+        let mut view = MapView::default();
+        view.focus((2.3, 48.9));
+        assert_eq!(nearest_marker(&markers, &view), Some(&markers[1]));
+    }
+}
@@ -1,67 +1,252 @@
-use self::config::{ensure_app_files_exist, Settings};
+use self::config::Settings;
+use self::constants::{
+    DASHBOARD_TAB, LOG_STATS_REFRESH_MS, MAP_TAB, RECENT_ACTIVITY_SECS, TABLE_TAB, TIMELINE_TAB,
+};
 use args::Args;
-use clap::Parser;
+use blocking::{BlockPreview, BlockTarget};
+use crawler::{ActivityRecord, Annotation, Enrichment, GeolocationRecord, Progress, Reputation};
+use dashboard::LogSummary;
+use detail::DetailView;
+use editor::AnnotationEditor;
+use filter::SearchFilter;
+use ip_blocking::{Action, Fail2banBan};
 use journal_parser::{journal::Journal, models::LogEntry};
+use map::{nearest_marker, MapMarker, MapView};
 use ratatui::widgets::TableState;
-use sshd_logs::SshdLogs;
-use std::net::Ipv4Addr;
+use sshd_logs::{Allowlist, LogsByIp, SshEvent, SshdLogs};
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    net::Ipv4Addr,
+    time::{Duration, Instant},
+};
+use table::{RowStats, SortColumn, TableSort};
+use time_range::{TimeRange, TimeRangePicker};
+use timeline::{local_utc_offset, timeline, BucketSize, TimelineBucket};
 
-mod args;
+pub mod args;
+pub mod blocking;
 pub mod config;
 pub mod constants;
+pub mod dashboard;
+pub mod detail;
+pub mod editor;
+pub mod filter;
+pub mod map;
+pub mod table;
+pub mod time_range;
+pub mod timeline;
 
 pub(crate) struct App {
-    pub args: Args,
     pub settings: Settings,
     pub tab_titles: Vec<&'static str>,
     pub tab_index: usize,
     pub ssh_table_state: TableState,
+    pub table_sort: TableSort,
+    /// Rows shown at once, updated with every draw of the table
+    pub table_page_size: usize,
+    /// Log statistics per ip of both the analysed and the allowlisted rows
+    pub row_stats: HashMap<Ipv4Addr, RowStats>,
     pub ssh_logs: Vec<(Ipv4Addr, Vec<LogEntry>, usize)>,
-    pub map_locations: Vec<(f64, f64)>,
+    /// Rows of allowlisted ips, left out of the analysis
+    pub allowlisted_logs: Vec<(Ipv4Addr, Vec<LogEntry>, usize)>,
+    pub allowlist: Allowlist,
+    /// Shows the allowlisted rows instead of the analysed ones
+    pub show_allowlisted: bool,
+    /// Latest geolocation per ip
+    pub locations: HashMap<Ipv4Addr, GeolocationRecord>,
+    pub map_view: MapView,
+    pub unknown_locations: HashSet<Ipv4Addr>,
+    pub reputations: HashMap<Ipv4Addr, Reputation>,
+    pub enrichments: HashMap<Ipv4Addr, Enrichment>,
+    pub lookup_progress: Progress,
+    pub annotations: HashMap<Ipv4Addr, Annotation>,
+    pub annotation_editor: Option<AnnotationEditor>,
+    /// Only rows with this tag are shown
+    pub tag_filter: Option<String>,
+    /// Ips with an active firewall ban
+    pub banned: HashSet<Ipv4Addr>,
+    pub block_preview: Option<BlockPreview>,
+    /// Active fail2ban bans per ip, one per jail
+    pub fail2ban_bans: HashMap<Ipv4Addr, Vec<Fail2banBan>>,
+    /// Detail pane of the selected ip below the ssh table
+    pub detail: Option<DetailView>,
+    /// Query of the `/` search
+    pub search_query: String,
+    pub editing_search: bool,
+    /// Why the current query is invalid, the last valid one stays applied
+    pub search_error: Option<String>,
+    /// Latest failed background action, shown until the next key press
+    pub status_error: Option<String>,
+    /// Applied query with its parsed filter
+    search_filter: Option<(String, SearchFilter)>,
+    /// Ips matched by the search filter, none without a filter
+    search_matches: Option<HashSet<Ipv4Addr>>,
+    /// Positions of the rows passing the tag and search filters in the shown rows, in the
+    /// chosen order
+    visible_rows: Vec<usize>,
+    pub timeline_bucket: BucketSize,
+    /// Authentications of the analysed ips over the loaded time range
+    pub timeline: Vec<TimelineBucket>,
+    pub log_summary: LogSummary,
+    /// Cursor of the latest read journal entry, the journal is followed from there
+    pub journal_cursor: Option<String>,
+    /// When ips got new logs, until their detail pane is opened
+    recent_activity: HashMap<Ipv4Addr, Instant>,
+    /// New logs arrived since the last calculation of the timeline and summary
+    log_stats_outdated: bool,
+    log_stats_refreshed: Instant,
+    /// Systemd units the logs are read of
+    pub units: Vec<String>,
+    pub time_range: TimeRange,
+    pub time_range_picker: Option<TimeRangePicker>,
 }
 
-impl Default for App {
-    fn default() -> Self {
-        let args = Args::parse();
-        let settings = Settings::new();
-        ensure_app_files_exist();
-        let sshd_output = Journal::with_service("sshd")
-            .since("yesterday")
-            .no_pager()
-            .build()
-            .read()
-            .unwrap();
-        let mut ssh_logs: Vec<(Ipv4Addr, Vec<LogEntry>, usize)> = sshd_output
-            .by_ips()
-            .unwrap()
-            .into_iter()
-            .map(|(ip_key, log_value)| {
-                let mut log_amount = 0;
-                let logs: Vec<LogEntry> = log_value
-                    .into_iter()
-                    // Yep, not nice
-                    .map(|e| {
-                        log_amount += 1;
-                        e.clone()
-                    })
-                    .collect();
-                (ip_key, logs, log_amount)
-            })
-            .collect();
-        ssh_logs.sort_by(|(_, _, amount_1), (_, _, amount_2)| amount_2.cmp(amount_1));
-        Self {
-            args,
+impl App {
+    /// Reads the logs of the time range of the args or the config
+    pub fn new(
+        args: Args,
+        settings: Settings,
+        allowlist: Allowlist,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let time_range = TimeRange {
+            since: args
+                .since
+                .to_owned()
+                .unwrap_or_else(|| settings.journal.since.to_owned()),
+            until: args
+                .until
+                .to_owned()
+                .or_else(|| settings.journal.until.to_owned()),
+        };
+        let units = if args.units.is_empty() {
+            settings.journal.units.to_owned()
+        } else {
+            args.units.to_owned()
+        };
+        let mut app = Self {
             settings,
-            tab_titles: vec!["SSH Logs", "Map"],
-            tab_index: 0,
+            tab_titles: vec!["Dashboard", "SSH Logs", "Map", "Timeline"],
+            tab_index: DASHBOARD_TAB,
             ssh_table_state: TableState::default(),
-            ssh_logs,
-            map_locations: vec![],
+            table_sort: TableSort::default(),
+            table_page_size: 1,
+            row_stats: HashMap::new(),
+            ssh_logs: Vec::new(),
+            allowlisted_logs: Vec::new(),
+            allowlist,
+            show_allowlisted: false,
+            locations: HashMap::new(),
+            map_view: MapView::default(),
+            unknown_locations: HashSet::new(),
+            reputations: HashMap::new(),
+            enrichments: HashMap::new(),
+            lookup_progress: Progress::default(),
+            annotations: HashMap::new(),
+            annotation_editor: None,
+            tag_filter: None,
+            banned: HashSet::new(),
+            block_preview: None,
+            fail2ban_bans: HashMap::new(),
+            detail: None,
+            search_query: String::new(),
+            editing_search: false,
+            search_error: None,
+            status_error: None,
+            search_filter: None,
+            search_matches: None,
+            visible_rows: Vec::new(),
+            timeline_bucket: BucketSize::Hour,
+            timeline: Vec::new(),
+            log_summary: LogSummary::default(),
+            journal_cursor: None,
+            recent_activity: HashMap::new(),
+            log_stats_outdated: false,
+            log_stats_refreshed: Instant::now(),
+            units,
+            time_range,
+            time_range_picker: None,
+        };
+        app.load_logs()?;
+        Ok(app)
+    }
+
+    /// Replaces all rows with the logs of the time range
+    fn load_logs(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let units: Vec<&str> = self.units.iter().map(String::as_str).collect();
+        let mut journal = Journal::with_services(&units)
+            .since(&self.time_range.since)
+            .no_pager();
+        if let Some(until) = &self.time_range.until {
+            journal = journal.until(until);
+        }
+        let sshd_output = journal.build().read()?;
+        let (ssh_logs, allowlisted_logs) = sshd_output.by_ips_with_allowlist(&self.allowlist)?;
+        self.ssh_logs = to_rows(ssh_logs);
+        self.allowlisted_logs = to_rows(allowlisted_logs);
+        self.row_stats = self
+            .ssh_logs
+            .iter()
+            .chain(&self.allowlisted_logs)
+            .map(|(ip, logs, _)| {
+                let events: Vec<SshEvent> = logs.iter().filter_map(SshEvent::from_log).collect();
+                (*ip, RowStats::from_events(&events))
+            })
+            .collect();
+        if self.journal_cursor.is_none() {
+            self.journal_cursor = sshd_output.logs.last().map(|log| log.cursor.to_owned());
         }
+        self.ssh_table_state.select(None);
+        self.detail = None;
+        self.update_timeline();
+        self.update_log_summary();
+        self.update_search_matches();
+        self.update_visible_rows();
+        Ok(())
+    }
+
+    pub fn open_time_range_picker(&mut self) {
+        self.time_range_picker = Some(TimeRangePicker::new(&self.time_range));
+    }
+
+    pub fn close_time_range_picker(&mut self) {
+        self.time_range_picker = None;
+    }
+
+    /// Reloads the logs with the picked time range, returns whether they were reloaded.
+    /// The picker stays open with the error if the logs can't be read
+    pub fn confirm_time_range_picker(&mut self) -> bool {
+        let Some(range) = self
+            .time_range_picker
+            .as_mut()
+            .and_then(TimeRangePicker::confirm)
+        else {
+            return false;
+        };
+        let previous = std::mem::replace(&mut self.time_range, range);
+        match self.load_logs() {
+            Ok(()) => {
+                self.time_range_picker = None;
+                true
+            }
+            Err(err) => {
+                self.time_range = previous;
+                if let Some(picker) = &mut self.time_range_picker {
+                    picker.error = Some(err.to_string());
+                }
+                false
+            }
+        }
+    }
+
+    /// Events of the analysed rows
+    pub fn ssh_events(&self) -> Vec<SshEvent> {
+        self.ssh_logs
+            .iter()
+            .flat_map(|(_, logs, _)| logs.iter().filter_map(SshEvent::from_log))
+            .collect()
     }
-}
 
-impl App {
     pub fn next_tab(&mut self) {
         self.tab_index = (self.tab_index + 1) % self.tab_titles.len();
     }
@@ -74,45 +259,755 @@ impl App {
         }
     }
 
+    pub fn on_table_tab(&self) -> bool {
+        self.tab_index == TABLE_TAB
+    }
+
+    pub fn on_map_tab(&self) -> bool {
+        self.tab_index == MAP_TAB
+    }
+
+    pub fn on_timeline_tab(&self) -> bool {
+        self.tab_index == TIMELINE_TAB
+    }
+
     pub fn down_row(&mut self) {
         // Only on table tab
-        if self.tab_index == 0 {
+        if self.on_table_tab() {
+            let rows = self.visible_logs().len();
             let i = match self.ssh_table_state.selected() {
-                Some(i) => {
-                    if i >= self.ssh_logs.len() - 1 {
-                        0
-                    } else {
-                        i + 1
-                    }
-                }
-                None => 0,
+                _ if rows == 0 => None,
+                Some(i) if i + 1 < rows => Some(i + 1),
+                _ => Some(0),
             };
-            self.ssh_table_state.select(Some(i));
+            self.ssh_table_state.select(i);
         }
     }
 
     pub fn up_row(&mut self) {
         // Only on table tab
-        if self.tab_index == 0 {
+        if self.on_table_tab() {
+            let rows = self.visible_logs().len();
             let i = match self.ssh_table_state.selected() {
-                Some(i) => {
-                    if i == 0 {
-                        self.ssh_logs.len() - 1
-                    } else {
-                        i - 1
-                    }
-                }
-                None => 0,
+                _ if rows == 0 => None,
+                Some(i) if i > 0 && i < rows => Some(i - 1),
+                Some(_) => Some(rows - 1),
+                None => Some(0),
             };
+            self.ssh_table_state.select(i);
+        }
+    }
+
+    /// Moves the selection by a page of rows, stops at the first and last row
+    pub fn page_down(&mut self) {
+        let rows = self.visible_logs().len();
+        if self.on_table_tab() && rows > 0 {
+            let i = self
+                .ssh_table_state
+                .selected()
+                .map_or(0, |i| i + self.table_page_size);
+            self.ssh_table_state.select(Some(i.min(rows - 1)));
+        }
+    }
+
+    pub fn page_up(&mut self) {
+        if self.on_table_tab() && !self.visible_logs().is_empty() {
+            let i = self
+                .ssh_table_state
+                .selected()
+                .map_or(0, |i| i.saturating_sub(self.table_page_size));
             self.ssh_table_state.select(Some(i));
         }
     }
 
-    pub fn mut_ssh_logs(&self) -> Vec<(Ipv4Addr, Vec<LogEntry>, usize)> {
-        self.ssh_logs.to_owned()
+    pub fn first_row(&mut self) {
+        if self.on_table_tab() && !self.visible_logs().is_empty() {
+            self.ssh_table_state.select(Some(0));
+        }
+    }
+
+    pub fn last_row(&mut self) {
+        let rows = self.visible_logs().len();
+        if self.on_table_tab() && rows > 0 {
+            self.ssh_table_state.select(Some(rows - 1));
+        }
+    }
+
+    pub fn next_sort_column(&mut self) {
+        self.table_sort.column = self.table_sort.column.next();
+        self.resort_visible_rows();
+    }
+
+    pub fn reverse_sort(&mut self) {
+        self.table_sort.descending = !self.table_sort.descending;
+        self.resort_visible_rows();
+    }
+
+    /// Keeps the selected ip selected after the rows moved
+    fn select_ip(&mut self, ip: Option<Ipv4Addr>) {
+        let position = ip.and_then(|ip| {
+            self.visible_logs()
+                .iter()
+                .position(|(row_ip, _, _)| *row_ip == ip)
+        });
+        self.ssh_table_state.select(position);
+    }
+
+    /// Rows of the ssh table after applying the tag and search filters, in the chosen order
+    pub fn visible_logs(&self) -> Vec<&(Ipv4Addr, Vec<LogEntry>, usize)> {
+        let rows = self.shown_rows();
+        self.visible_rows
+            .iter()
+            .filter_map(|position| rows.get(*position))
+            .collect()
+    }
+
+    fn shown_rows(&self) -> &[(Ipv4Addr, Vec<LogEntry>, usize)] {
+        if self.show_allowlisted {
+            &self.allowlisted_logs
+        } else {
+            &self.ssh_logs
+        }
+    }
+
+    /// Filters and sorts the shown rows again, needed whenever the rows, the filters or the
+    /// sort change
+    fn update_visible_rows(&mut self) {
+        let rows = self.shown_rows();
+        let mut visible_rows: Vec<usize> = rows
+            .iter()
+            .enumerate()
+            .filter(|(_, (ip, _, _))| match &self.tag_filter {
+                Some(tag) => self
+                    .annotations
+                    .get(ip)
+                    .is_some_and(|annotation| annotation.has_tag(tag)),
+                None => true,
+            })
+            .filter(|(_, (ip, _, _))| match &self.search_matches {
+                Some(matches) => matches.contains(ip),
+                None => true,
+            })
+            .map(|(position, _)| position)
+            .collect();
+        visible_rows.sort_by(|position_1, position_2| {
+            let ordering = self.compare_rows(&rows[*position_1], &rows[*position_2]);
+            if self.table_sort.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+        self.visible_rows = visible_rows;
+    }
+
+    /// Updates the visible rows of unchanged rows, the selected ip stays selected
+    fn resort_visible_rows(&mut self) {
+        let selected = self.selected_ip();
+        self.update_visible_rows();
+        self.select_ip(selected);
+    }
+
+    /// Ascending order of the rows by the sort column, ties are ordered by ip
+    fn compare_rows(
+        &self,
+        (ip_1, _, amount_1): &(Ipv4Addr, Vec<LogEntry>, usize),
+        (ip_2, _, amount_2): &(Ipv4Addr, Vec<LogEntry>, usize),
+    ) -> Ordering {
+        let no_stats = RowStats::default();
+        let stats_1 = self.row_stats.get(ip_1).unwrap_or(&no_stats);
+        let stats_2 = self.row_stats.get(ip_2).unwrap_or(&no_stats);
+        let location = |ip| self.locations.get(ip);
+        let ordering = match self.table_sort.column {
+            SortColumn::Ip => Ordering::Equal,
+            SortColumn::Hostname => {
+                let hostname = |ip| {
+                    self.enrichments
+                        .get(ip)
+                        .and_then(|enrichment: &Enrichment| enrichment.ptr.as_deref())
+                };
+                hostname(ip_1).cmp(&hostname(ip_2))
+            }
+            SortColumn::Country => location(ip_1)
+                .and_then(|location| location.country_name.as_deref())
+                .cmp(&location(ip_2).and_then(|location| location.country_name.as_deref())),
+            SortColumn::Org => location(ip_1)
+                .and_then(|location| location.org.as_deref())
+                .cmp(&location(ip_2).and_then(|location| location.org.as_deref())),
+            SortColumn::Logs => amount_1.cmp(amount_2),
+            SortColumn::FirstSeen => stats_1.first_seen.cmp(&stats_2.first_seen),
+            SortColumn::LastSeen => stats_1.last_seen.cmp(&stats_2.last_seen),
+            SortColumn::Usernames => stats_1.usernames.cmp(&stats_2.usernames),
+            SortColumn::Logins => stats_1.logins.cmp(&stats_2.logins),
+            SortColumn::Reputation => {
+                let score = |ip| {
+                    self.reputations.get(ip).map(|reputation: &Reputation| {
+                        (
+                            reputation
+                                .abuse
+                                .as_ref()
+                                .map(|abuse| abuse.abuse_confidence_score),
+                            reputation.blocklists.len(),
+                        )
+                    })
+                };
+                score(ip_1).cmp(&score(ip_2))
+            }
+        };
+        ordering.then_with(|| ip_1.cmp(ip_2))
+    }
+
+    /// Markers of the located visible rows, ips at the same location share one
+    pub fn map_markers(&self) -> Vec<MapMarker> {
+        let mut markers: HashMap<(u64, u64), MapMarker> = HashMap::new();
+        for (ip, _, _) in self.visible_logs() {
+            let Some(location) = self.locations.get(ip) else {
+                continue;
+            };
+            let attempts = self
+                .row_stats
+                .get(ip)
+                .map(|stats| stats.attempts)
+                .unwrap_or_default();
+            markers
+                .entry((location.latitude.to_bits(), location.longitude.to_bits()))
+                .or_insert_with(|| MapMarker {
+                    latitude: location.latitude,
+                    longitude: location.longitude,
+                    country: location.country_name.to_owned(),
+                    ips: Vec::new(),
+                })
+                .ips
+                .push((*ip, attempts));
+        }
+        markers
+            .into_values()
+            .map(|mut marker| {
+                marker
+                    .ips
+                    .sort_by(|(_, attempts_1), (_, attempts_2)| attempts_2.cmp(attempts_1));
+                marker
+            })
+            .collect()
+    }
+
+    /// Switches to the map, centered on the location of the selected ip
+    pub fn show_selected_on_map(&mut self) {
+        if !self.on_table_tab() {
+            return;
+        }
+        let Some(location) = self.selected_ip().and_then(|ip| self.locations.get(&ip)) else {
+            return;
+        };
+        self.map_view.focus((location.longitude, location.latitude));
+        self.tab_index = MAP_TAB;
+    }
+
+    /// Switches to the table, with the ip of the marker under the cursor with the most
+    /// attempts selected
+    pub fn show_marker_in_table(&mut self) {
+        if !self.on_map_tab() {
+            return;
+        }
+        let markers = self.map_markers();
+        let Some((ip, _)) = nearest_marker(&markers, &self.map_view)
+            .and_then(|marker| marker.ips.first())
+            .copied()
+        else {
+            return;
+        };
+        self.close_detail();
+        self.tab_index = TABLE_TAB;
+        self.select_ip(Some(ip));
+    }
+
+    pub fn open_search(&mut self) {
+        self.editing_search = true;
+    }
+
+    /// Stops editing, the query stays applied
+    pub fn finish_search(&mut self) {
+        self.editing_search = false;
+    }
+
+    pub fn clear_search(&mut self) {
+        self.editing_search = false;
+        self.search_query.clear();
+        self.update_search();
+    }
+
+    pub fn push_search(&mut self, character: char) {
+        self.search_query.push(character);
+        self.update_search();
+    }
+
+    pub fn pop_search(&mut self) {
+        self.search_query.pop();
+        self.update_search();
+    }
+
+    /// Query of the applied search filter
+    pub fn active_search(&self) -> Option<&str> {
+        self.search_filter.as_ref().map(|(query, _)| query.as_str())
+    }
+
+    fn update_search(&mut self) {
+        match SearchFilter::parse(&self.search_query) {
+            Ok(filter) => {
+                let query = self.search_query.trim();
+                self.search_filter = (!query.is_empty()).then(|| (query.to_owned(), filter));
+                self.search_error = None;
+            }
+            Err(err) => self.search_error = Some(err),
+        }
+        self.update_search_matches();
+        self.update_visible_rows();
+        self.ssh_table_state.select(None);
+    }
+
+    fn update_search_matches(&mut self) {
+        self.search_matches = self.search_filter.as_ref().map(|(_, filter)| {
+            self.ssh_logs
+                .iter()
+                .chain(&self.allowlisted_logs)
+                .filter(|(ip, logs, _)| filter.matches(ip, logs, self.locations.get(ip)))
+                .map(|(ip, _, _)| *ip)
+                .collect()
+        });
+    }
+
+    /// Re-evaluates the search filter for a single ip
+    fn update_search_match(&mut self, ip: Ipv4Addr) {
+        let Some((_, filter)) = &self.search_filter else {
+            return;
+        };
+        let matched = filter.matches(&ip, self.logs_of(&ip), self.locations.get(&ip));
+        if let Some(search_matches) = &mut self.search_matches {
+            if matched {
+                search_matches.insert(ip);
+            } else {
+                search_matches.remove(&ip);
+            }
+        }
+    }
+
+    pub fn selected_ip(&self) -> Option<Ipv4Addr> {
+        let selected = self.ssh_table_state.selected()?;
+        self.visible_logs().get(selected).map(|(ip, _, _)| *ip)
     }
 
-    pub fn add_geolocation(&mut self, lat: f64, lng: f64) {
-        self.map_locations.push((lat, lng));
+    /// Logs of the ip in the ssh table, allowlisted or not
+    pub fn logs_of(&self, ip: &Ipv4Addr) -> &[LogEntry] {
+        self.ssh_logs
+            .iter()
+            .chain(&self.allowlisted_logs)
+            .find(|(row_ip, _, _)| row_ip == ip)
+            .map(|(_, logs, _)| logs.as_slice())
+            .unwrap_or_default()
     }
+
+    /// Opens the detail pane of the selected ip, returns the ip to load its saved data
+    pub fn open_detail(&mut self) -> Option<Ipv4Addr> {
+        if !self.on_table_tab() {
+            return None;
+        }
+        let ip = self.selected_ip()?;
+        self.recent_activity.remove(&ip);
+        self.detail = Some(DetailView::new(ip));
+        Some(ip)
+    }
+
+    pub fn close_detail(&mut self) {
+        self.detail = None;
+    }
+
+    pub fn scroll_detail_down(&mut self, page: bool) {
+        let Some(ip) = self.detail.as_ref().map(|detail| detail.ip) else {
+            return;
+        };
+        let lines = self.logs_of(&ip).len();
+        if let Some(detail) = &mut self.detail {
+            if page {
+                detail.page_down(lines);
+            } else {
+                detail.scroll_down(lines);
+            }
+        }
+    }
+
+    pub fn scroll_detail_up(&mut self, page: bool) {
+        if let Some(detail) = &mut self.detail {
+            if page {
+                detail.page_up();
+            } else {
+                detail.scroll_up();
+            }
+        }
+    }
+
+    pub fn larger_timeline_buckets(&mut self) {
+        self.timeline_bucket = self.timeline_bucket.larger();
+        self.update_timeline();
+    }
+
+    pub fn smaller_timeline_buckets(&mut self) {
+        self.timeline_bucket = self.timeline_bucket.smaller();
+        self.update_timeline();
+    }
+
+    fn update_timeline(&mut self) {
+        let events = self.ssh_events();
+        self.timeline = timeline(&events, self.timeline_bucket, local_utc_offset());
+    }
+
+    fn update_log_summary(&mut self) {
+        let events = self.ssh_events();
+        self.log_summary = LogSummary::from_events(&events);
+    }
+
+    /// Adds a log entry of the followed journal to the rows of its ip while keeping the
+    /// selection, returns its event to be saved. Entries without an ip are ignored
+    pub fn add_log_entry(&mut self, entry: LogEntry) -> Option<SshEvent> {
+        // New entries are beyond the end of a closed time range
+        if self.time_range.until.is_some() {
+            return None;
+        }
+        let event = SshEvent::from_log(&entry)?;
+        let ip = event.ipv4;
+        let selected = self.selected_ip();
+        let rows = if self.allowlist.contains(&ip) {
+            &mut self.allowlisted_logs
+        } else {
+            &mut self.ssh_logs
+        };
+        let mut row = match rows.iter().position(|(row_ip, _, _)| *row_ip == ip) {
+            Some(position) => rows.remove(position),
+            None => (ip, Vec::new(), 0),
+        };
+        row.1.push(entry);
+        row.2 += 1;
+        let events: Vec<SshEvent> = row.1.iter().filter_map(SshEvent::from_log).collect();
+        self.row_stats.insert(ip, RowStats::from_events(&events));
+        let insert_at = rows.partition_point(|(_, _, amount)| *amount >= row.2);
+        rows.insert(insert_at, row);
+        self.recent_activity.insert(ip, Instant::now());
+        self.log_stats_outdated = true;
+        self.update_visible_rows();
+        self.select_ip(selected);
+        Some(event)
+    }
+
+    /// Recalculates the statistics over all logs after new ones arrived, at most every
+    /// few seconds
+    pub fn refresh_log_stats(&mut self) {
+        if !self.log_stats_outdated
+            || self.log_stats_refreshed.elapsed() < Duration::from_millis(LOG_STATS_REFRESH_MS)
+        {
+            return;
+        }
+        let selected = self.selected_ip();
+        self.update_timeline();
+        self.update_log_summary();
+        if self.search_filter.is_some() {
+            self.update_search_matches();
+            self.update_visible_rows();
+            self.select_ip(selected);
+        }
+        self.log_stats_outdated = false;
+        self.log_stats_refreshed = Instant::now();
+    }
+
+    /// The ip got new logs within the last minutes which were not looked at yet
+    pub fn is_recently_active(&self, ip: &Ipv4Addr) -> bool {
+        self.recent_activity
+            .get(ip)
+            .is_some_and(|at| at.elapsed() < Duration::from_secs(RECENT_ACTIVITY_SECS))
+    }
+
+    /// Authentications of a single ip over its own time range
+    pub fn ip_timeline(&self, ip: &Ipv4Addr) -> Vec<TimelineBucket> {
+        let events: Vec<SshEvent> = self
+            .logs_of(ip)
+            .iter()
+            .filter_map(SshEvent::from_log)
+            .collect();
+        timeline(&events, self.timeline_bucket, local_utc_offset())
+    }
+
+    /// Ignores details of an ip whose pane was closed in the meantime
+    pub fn set_ip_details(
+        &mut self,
+        ip: Ipv4Addr,
+        geolocation: Option<GeolocationRecord>,
+        activity: Option<ActivityRecord>,
+    ) {
+        if let Some(detail) = self.detail.as_mut().filter(|detail| detail.ip == ip) {
+            detail.geolocation = geolocation;
+            detail.activity = activity;
+            detail.loaded = true;
+        }
+    }
+
+    pub fn open_annotation_editor(&mut self) {
+        if !self.on_table_tab() {
+            return;
+        }
+        if let Some(ip) = self.selected_ip() {
+            self.annotation_editor = Some(AnnotationEditor::new(ip, self.annotations.get(&ip)));
+        }
+    }
+
+    pub fn close_annotation_editor(&mut self) {
+        self.annotation_editor = None;
+    }
+
+    /// Closes the editor and applies the edited annotation, which is returned to be saved
+    pub fn finish_annotation_editor(&mut self) -> Option<(Ipv4Addr, Annotation)> {
+        let editor = self.annotation_editor.take()?;
+        let annotation = editor.annotation();
+        if annotation.is_empty() {
+            self.annotations.remove(&editor.ip);
+        } else {
+            self.annotations.insert(editor.ip, annotation.clone());
+        }
+        if self.tag_filter.is_some() {
+            self.resort_visible_rows();
+        }
+        Some((editor.ip, annotation))
+    }
+
+    /// Sorted tags of all annotated ips
+    pub fn known_tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = self
+            .annotations
+            .values()
+            .flat_map(|annotation| annotation.tags.iter().cloned())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        tags.sort();
+        tags
+    }
+
+    /// Cycles through no filter and every known tag
+    pub fn next_tag_filter(&mut self) {
+        let tags = self.known_tags();
+        self.tag_filter = match &self.tag_filter {
+            None => tags.first().cloned(),
+            Some(current) => tags
+                .iter()
+                .position(|tag| tag == current)
+                .and_then(|position| tags.get(position + 1))
+                .cloned(),
+        };
+        self.update_visible_rows();
+        self.ssh_table_state.select(None);
+    }
+
+    pub fn toggle_allowlisted(&mut self) {
+        self.show_allowlisted = !self.show_allowlisted;
+        self.update_visible_rows();
+        self.ssh_table_state.select(None);
+    }
+
+    /// Moves the selected ip into or out of the allowlist, returns the ip and whether it is
+    /// allowlisted now. Ips allowed by a configured network can only be removed in the config
+    pub fn allowlist_selected(&mut self) -> Option<(Ipv4Addr, bool)> {
+        if !self.on_table_tab() {
+            return None;
+        }
+        let ip = self.selected_ip()?;
+        let (from, to) = if self.show_allowlisted {
+            let mut allowlist = self.allowlist.clone();
+            if !allowlist.remove(&ip) || allowlist.contains(&ip) {
+                return None;
+            }
+            self.allowlist = allowlist;
+            (&mut self.allowlisted_logs, &mut self.ssh_logs)
+        } else {
+            self.allowlist.insert(ip);
+            (&mut self.ssh_logs, &mut self.allowlisted_logs)
+        };
+        let position = from.iter().position(|(row_ip, _, _)| *row_ip == ip)?;
+        let row = from.remove(position);
+        let insert_at = to.partition_point(|(_, _, amount)| *amount >= row.2);
+        to.insert(insert_at, row);
+        self.update_visible_rows();
+        self.ssh_table_state.select(None);
+        self.update_timeline();
+        self.update_log_summary();
+        Some((ip, !self.show_allowlisted))
+    }
+
+    /// Previews the ban of the top offenders which are not banned yet
+    pub fn open_block_preview(&mut self) {
+        let detections: Vec<(Ipv4Addr, usize)> = self
+            .ssh_logs
+            .iter()
+            .filter(|(ip, _, _)| !self.banned.contains(ip))
+            .map(|(ip, _, amount)| (*ip, *amount))
+            .collect();
+        let blocking = &self.settings.blocking;
+        let ips = blocking.policy().offenders(&detections);
+        let duration = blocking.ban_duration();
+        let actions = blocking
+            .firewall(blocking.backend)
+            .ban_actions(&ips, duration);
+        let target = BlockTarget::Firewall {
+            backend: blocking.backend,
+            duration,
+        };
+        self.block_preview = Some(BlockPreview::new(target, ips, actions));
+    }
+
+    /// Previews banning the selected ip in the configured fail2ban jail
+    pub fn open_fail2ban_ban_preview(&mut self) {
+        if !self.on_table_tab() || !self.settings.fail2ban.enabled {
+            return;
+        }
+        let Some(ip) = self.selected_ip() else {
+            return;
+        };
+        let fail2ban = &self.settings.fail2ban;
+        let actions = vec![fail2ban.client().ban_action(&fail2ban.jail, &[ip])];
+        let target = BlockTarget::Fail2banBan {
+            jail: fail2ban.jail.to_owned(),
+        };
+        self.block_preview = Some(BlockPreview::new(target, vec![ip], actions));
+    }
+
+    /// Previews lifting the fail2ban bans of the selected ip in all its jails
+    pub fn open_fail2ban_unban_preview(&mut self) {
+        if !self.on_table_tab() || !self.settings.fail2ban.enabled {
+            return;
+        }
+        let Some(ip) = self.selected_ip() else {
+            return;
+        };
+        let Some(bans) = self.fail2ban_bans.get(&ip) else {
+            return;
+        };
+        let client = self.settings.fail2ban.client();
+        let jails: Vec<String> = bans.iter().map(|ban| ban.jail.to_owned()).collect();
+        let actions = jails
+            .iter()
+            .map(|jail| client.unban_action(jail, &[ip]))
+            .collect();
+        let target = BlockTarget::Fail2banUnban { jails };
+        self.block_preview = Some(BlockPreview::new(target, vec![ip], actions));
+    }
+
+    pub fn close_block_preview(&mut self) {
+        self.block_preview = None;
+    }
+
+    /// Marks the preview as applying, returns what to apply in the background
+    pub fn start_block_preview(&mut self) -> Option<(BlockTarget, Vec<Ipv4Addr>, Vec<Action>)> {
+        let preview = self.block_preview.as_mut()?;
+        if preview.applying || preview.is_applied() || preview.ips.is_empty() {
+            return None;
+        }
+        preview.applying = true;
+        Some((
+            preview.target.clone(),
+            preview.ips.clone(),
+            preview.actions.clone(),
+        ))
+    }
+
+    /// Takes over the outcome of the applied actions, the preview may be closed by now
+    pub fn finish_block_preview(
+        &mut self,
+        target: BlockTarget,
+        ips: Vec<Ipv4Addr>,
+        status: Result<(), String>,
+    ) {
+        if status.is_ok() {
+            match &target {
+                BlockTarget::Firewall { .. } => self.banned.extend(ips.iter().copied()),
+                // Fresh fail2ban bans show up with the next read of its database
+                BlockTarget::Fail2banBan { .. } => {}
+                BlockTarget::Fail2banUnban { .. } => {
+                    for ip in &ips {
+                        self.fail2ban_bans.remove(ip);
+                    }
+                }
+            }
+        }
+        if let Some(preview) = self
+            .block_preview
+            .as_mut()
+            .filter(|preview| preview.applying && preview.target == target && preview.ips == ips)
+        {
+            preview.applying = false;
+            preview.status = Some(status);
+        }
+    }
+
+    pub fn set_annotations(&mut self, annotations: HashMap<Ipv4Addr, Annotation>) {
+        self.annotations = annotations;
+        if self.tag_filter.is_some() {
+            self.resort_visible_rows();
+        }
+    }
+
+    pub fn set_fail2ban_bans(&mut self, bans: Vec<Fail2banBan>) {
+        self.fail2ban_bans.clear();
+        for ban in bans {
+            self.fail2ban_bans.entry(ban.ipv4).or_default().push(ban);
+        }
+    }
+
+    pub fn add_geolocation(&mut self, ip: Ipv4Addr, location: GeolocationRecord) {
+        self.locations.insert(ip, location);
+        self.unknown_locations.remove(&ip);
+        // Country terms only match once the location is known
+        let matches_country = self
+            .search_filter
+            .as_ref()
+            .is_some_and(|(_, filter)| filter.has_country_terms());
+        if matches_country {
+            self.update_search_match(ip);
+        }
+        if matches_country
+            || matches!(
+                self.table_sort.column,
+                SortColumn::Country | SortColumn::Org
+            )
+        {
+            self.resort_visible_rows();
+        }
+    }
+
+    pub fn add_unknown_location(&mut self, ip: Ipv4Addr) {
+        self.unknown_locations.insert(ip);
+    }
+
+    pub fn add_reputation(&mut self, ip: Ipv4Addr, reputation: Reputation) {
+        self.reputations.insert(ip, reputation);
+        if self.table_sort.column == SortColumn::Reputation {
+            self.resort_visible_rows();
+        }
+    }
+
+    pub fn add_enrichment(&mut self, ip: Ipv4Addr, enrichment: Enrichment) {
+        self.enrichments.insert(ip, enrichment);
+        if self.table_sort.column == SortColumn::Hostname {
+            self.resort_visible_rows();
+        }
+    }
+}
+
+/// Rows of the ssh table, the ips with the most logs first
+fn to_rows(logs_by_ip: LogsByIp) -> Vec<(Ipv4Addr, Vec<LogEntry>, usize)> {
+    let mut rows: Vec<(Ipv4Addr, Vec<LogEntry>, usize)> = logs_by_ip
+        .into_iter()
+        .map(|(ip, logs)| {
+            let logs: Vec<LogEntry> = logs.into_iter().cloned().collect();
+            let amount = logs.len();
+            (ip, logs, amount)
+        })
+        .collect();
+    rows.sort_by(|(_, _, amount_1), (_, _, amount_2)| amount_2.cmp(amount_1));
+    rows
 }
@@ -0,0 +1,127 @@
+use sshd_logs::{SshEvent, SshEventKind};
+use std::collections::HashSet;
+
+/// Columns the ssh table can be sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SortColumn {
+    Ip,
+    Hostname,
+    Country,
+    Org,
+    Logs,
+    FirstSeen,
+    LastSeen,
+    Usernames,
+    Logins,
+    Reputation,
+}
+
+impl SortColumn {
+    const ALL: [Self; 10] = [
+        Self::Ip,
+        Self::Hostname,
+        Self::Country,
+        Self::Org,
+        Self::Logs,
+        Self::FirstSeen,
+        Self::LastSeen,
+        Self::Usernames,
+        Self::Logins,
+        Self::Reputation,
+    ];
+
+    pub fn next(self) -> Self {
+        let position = Self::ALL.iter().position(|column| *column == self);
+        Self::ALL[position.map_or(0, |position| (position + 1) % Self::ALL.len())]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TableSort {
+    pub column: SortColumn,
+    pub descending: bool,
+}
+
+impl Default for TableSort {
+    /// The ips with the most logs first
+    fn default() -> Self {
+        Self {
+            column: SortColumn::Logs,
+            descending: true,
+        }
+    }
+}
+
+/// Statistics of the logs of an ip shown in the ssh table
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct RowStats {
+    /// Microseconds since the unix epoch
+    pub first_seen: Option<i64>,
+    pub last_seen: Option<i64>,
+    /// Authentication attempts
+    pub attempts: usize,
+    /// Distinct usernames tried
+    pub usernames: usize,
+    /// Successful logins
+    pub logins: usize,
+}
+
+impl RowStats {
+    pub fn from_events(events: &[SshEvent]) -> Self {
+        let usernames: HashSet<&str> = events
+            .iter()
+            .filter_map(|event| event.user.as_deref())
+            .collect();
+        Self {
+            first_seen: events.iter().map(|event| event.timestamp).min(),
+            last_seen: events.iter().map(|event| event.timestamp).max(),
+            attempts: events
+                .iter()
+                .filter(|event| event.kind.is_attempt())
+                .count(),
+            usernames: usernames.len(),
+            logins: events
+                .iter()
+                .filter(|event| event.kind == SshEventKind::Accepted)
+                .count(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    fn event(timestamp: i64, user: &str, kind: SshEventKind) -> SshEvent {
+        SshEvent {
+            ipv4: Ipv4Addr::new(203, 0, 113, 7),
+            timestamp,
+            user: Some(user.to_owned()),
+            port: Some(22),
+            kind,
+            message: String::new(),
+            cursor: String::new(),
+        }
+    }
+
+    #[test]
+    fn should_collect_row_stats() {
+        let events = [
+            event(300, "root", SshEventKind::FailedPassword),
+            event(100, "admin", SshEventKind::InvalidUser),
+            event(200, "root", SshEventKind::Accepted),
+        ];
+        assert_eq!(
+            RowStats::from_events(&events),
+            RowStats {
+                first_seen: Some(100),
+                last_seen: Some(300),
+                attempts: 2,
+                usernames: 2,
+                logins: 1,
+            }
+        );
+        assert_eq!(RowStats::from_events(&[]), RowStats::default());
+    }
+}
@@ -0,0 +1,188 @@
+use std::fmt;
+
+/// Time range the logs are read from, in the time syntax of journalctl
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TimeRange {
+    pub since: String,
+    /// Logs are read up to now without an end
+    pub until: Option<String>,
+}
+
+impl fmt::Display for TimeRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.until {
+            Some(until) => write!(f, "{} to {}", self.since, until),
+            None => write!(f, "since {}", self.since),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TimeRangePreset {
+    LastHour,
+    LastDay,
+    LastWeek,
+    Custom,
+}
+
+impl TimeRangePreset {
+    pub const ALL: [Self; 4] = [Self::LastHour, Self::LastDay, Self::LastWeek, Self::Custom];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::LastHour => "Last hour",
+            Self::LastDay => "Last 24 hours",
+            Self::LastWeek => "Last 7 days",
+            Self::Custom => "Custom",
+        }
+    }
+
+    /// Custom ranges are entered in the picker
+    fn range(&self) -> Option<TimeRange> {
+        let since = match self {
+            Self::LastHour => "-1h",
+            Self::LastDay => "-24h",
+            Self::LastWeek => "-7d",
+            Self::Custom => return None,
+        };
+        Some(TimeRange {
+            since: since.to_owned(),
+            until: None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TimeRangeField {
+    Since,
+    Until,
+}
+
+/// Popup state while a new time range is picked
+#[derive(Debug)]
+pub(crate) struct TimeRangePicker {
+    /// Index into [`TimeRangePreset::ALL`]
+    pub selected: usize,
+    /// The custom range is entered
+    pub editing: bool,
+    pub since: String,
+    pub until: String,
+    pub field: TimeRangeField,
+    /// Why the last picked range could not be read
+    pub error: Option<String>,
+}
+
+impl TimeRangePicker {
+    pub fn new(current: &TimeRange) -> Self {
+        Self {
+            selected: 0,
+            editing: false,
+            since: current.since.to_owned(),
+            until: current.until.to_owned().unwrap_or_default(),
+            field: TimeRangeField::Since,
+            error: None,
+        }
+    }
+
+    pub fn preset(&self) -> TimeRangePreset {
+        TimeRangePreset::ALL[self.selected]
+    }
+
+    pub fn next(&mut self) {
+        if !self.editing {
+            self.selected = (self.selected + 1) % TimeRangePreset::ALL.len();
+        }
+    }
+
+    pub fn previous(&mut self) {
+        if !self.editing {
+            self.selected =
+                (self.selected + TimeRangePreset::ALL.len() - 1) % TimeRangePreset::ALL.len();
+        }
+    }
+
+    pub fn next_field(&mut self) {
+        self.field = match self.field {
+            TimeRangeField::Since => TimeRangeField::Until,
+            TimeRangeField::Until => TimeRangeField::Since,
+        };
+    }
+
+    pub fn push(&mut self, character: char) {
+        if let Some(field) = self.active_field() {
+            field.push(character);
+        }
+    }
+
+    pub fn pop(&mut self) {
+        if let Some(field) = self.active_field() {
+            field.pop();
+        }
+    }
+
+    /// Returns the picked range, picking the custom one first starts entering it
+    pub fn confirm(&mut self) -> Option<TimeRange> {
+        if let Some(range) = self.preset().range() {
+            return Some(range);
+        }
+        if !self.editing {
+            self.editing = true;
+            return None;
+        }
+        let since = self.since.trim();
+        let until = self.until.trim();
+        if since.is_empty() {
+            self.error = Some("A start is required".to_owned());
+            return None;
+        }
+        Some(TimeRange {
+            since: since.to_owned(),
+            until: (!until.is_empty()).then(|| until.to_owned()),
+        })
+    }
+
+    fn active_field(&mut self) -> Option<&mut String> {
+        if !self.editing {
+            return None;
+        }
+        match self.field {
+            TimeRangeField::Since => Some(&mut self.since),
+            TimeRangeField::Until => Some(&mut self.until),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_pick_presets_and_custom_ranges() {
+        let current = TimeRange {
+            since: "yesterday".to_owned(),
+            until: None,
+        };
+        let mut picker = TimeRangePicker::new(&current);
+        picker.next();
+        assert_eq!(picker.confirm().unwrap().since, "-24h");
+
+        picker.previous();
+        picker.previous();
+        assert_eq!(picker.preset(), TimeRangePreset::Custom);
+        assert_eq!(picker.confirm(), None);
+        picker.push('x');
+        picker.next_field();
+        for character in "today".chars() {
+            picker.push(character);
+        }
+        picker.next();
+        assert_eq!(
+            picker.confirm(),
+            Some(TimeRange {
+                since: "yesterdayx".to_owned(),
+                until: Some("today".to_owned()),
+            })
+        );
+        assert_eq!(picker.confirm().unwrap().to_string(), "yesterdayx to today");
+    }
+}
@@ -0,0 +1,139 @@
+use chrono::Local;
+use sshd_logs::{SshEvent, SshEventKind};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BucketSize {
+    Minute,
+    Hour,
+    Day,
+}
+
+impl BucketSize {
+    pub fn seconds(&self) -> i64 {
+        match self {
+            Self::Minute => 60,
+            Self::Hour => 60 * 60,
+            Self::Day => 24 * 60 * 60,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Minute => "minute",
+            Self::Hour => "hour",
+            Self::Day => "day",
+        }
+    }
+
+    pub fn larger(self) -> Self {
+        match self {
+            Self::Minute => Self::Hour,
+            Self::Hour | Self::Day => Self::Day,
+        }
+    }
+
+    pub fn smaller(self) -> Self {
+        match self {
+            Self::Minute | Self::Hour => Self::Minute,
+            Self::Day => Self::Hour,
+        }
+    }
+}
+
+/// Authentications within one bucket of the timeline
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct TimelineBucket {
+    /// Seconds since the unix epoch
+    pub start: i64,
+    pub failed: u64,
+    pub successful: u64,
+}
+
+/// Buckets from the first to the last authentication, including the quiet ones in between.
+/// Buckets are aligned to the local time given by its offset to utc in seconds
+pub(crate) fn timeline<'a, I>(events: I, size: BucketSize, utc_offset: i64) -> Vec<TimelineBucket>
+where
+    I: IntoIterator<Item = &'a SshEvent>,
+{
+    let bucket_seconds = size.seconds();
+    let mut counts: Vec<(i64, bool)> = events
+        .into_iter()
+        .filter(|event| event.kind.is_attempt())
+        .map(|event| {
+            let local = event.timestamp.div_euclid(1_000_000) + utc_offset;
+            let start = local - local.rem_euclid(bucket_seconds) - utc_offset;
+            (start, event.kind == SshEventKind::Accepted)
+        })
+        .collect();
+    counts.sort_unstable();
+    let (Some((first, _)), Some((last, _))) = (counts.first(), counts.last()) else {
+        return Vec::new();
+    };
+    let mut buckets: Vec<TimelineBucket> = (0..=(last - first) / bucket_seconds)
+        .map(|index| TimelineBucket {
+            start: first + index * bucket_seconds,
+            ..Default::default()
+        })
+        .collect();
+    let first = *first;
+    for (start, successful) in counts {
+        let bucket = &mut buckets[((start - first) / bucket_seconds) as usize];
+        if successful {
+            bucket.successful += 1;
+        } else {
+            bucket.failed += 1;
+        }
+    }
+    buckets
+}
+
+/// Current offset of the local time to utc in seconds
+pub(crate) fn local_utc_offset() -> i64 {
+    i64::from(Local::now().offset().local_minus_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    fn event(seconds: i64, kind: SshEventKind) -> SshEvent {
+        SshEvent {
+            ipv4: Ipv4Addr::new(203, 0, 113, 7),
+            timestamp: seconds * 1_000_000,
+            user: None,
+            port: None,
+            kind,
+            message: String::new(),
+            cursor: String::new(),
+        }
+    }
+
+    #[test]
+    fn should_count_authentications_per_bucket() {
+        let events = [
+            event(3_700, SshEventKind::FailedPassword),
+            event(3_650, SshEventKind::InvalidUser),
+            event(100, SshEventKind::Accepted),
+            event(10_900, SshEventKind::FailedPublickey),
+            event(200, SshEventKind::FailedPassword),
+        ];
+        let bucket = |start, failed, successful| TimelineBucket {
+            start,
+            failed,
+            successful,
+        };
+        assert_eq!(
+            timeline(&events, BucketSize::Hour, 0),
+            vec![
+                bucket(0, 1, 1),
+                bucket(3_600, 1, 0),
+                bucket(7_200, 0, 0),
+                bucket(10_800, 1, 0),
+            ]
+        );
+        // Half an hour east of utc the hours start half past
+        assert_eq!(timeline(&events, BucketSize::Hour, 1_800)[0].start, -1_800);
+        assert!(timeline(&[], BucketSize::Day, 0).is_empty());
+    }
+}
@@ -0,0 +1,222 @@
+use crate::app::args::ExportFormat;
+use crate::app::config::{Settings, DEFAULT_CONFIG};
+use crate::app::App;
+use chrono::{DateTime, Utc};
+use crawler::{AppCrawler, Blocklist, Crawler, CrawlerError};
+use serde::Serialize;
+use std::{
+    fs,
+    io::{self, Write},
+    net::Ipv4Addr,
+    path::Path,
+};
+
+/// Exported statistics of the logs of an ip
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct ExportRow {
+    pub ip: Ipv4Addr,
+    pub logs: usize,
+    pub attempts: usize,
+    /// Rfc 3339 in utc
+    pub first_seen: Option<String>,
+    pub last_seen: Option<String>,
+    pub usernames: usize,
+    pub logins: usize,
+}
+
+impl ExportRow {
+    const CSV_HEADER: &'static str = "ip,logs,attempts,first_seen,last_seen,usernames,logins";
+
+    fn to_csv(&self) -> String {
+        format!(
+            "{},{},{},{},{},{},{}",
+            self.ip,
+            self.logs,
+            self.attempts,
+            self.first_seen.as_deref().unwrap_or_default(),
+            self.last_seen.as_deref().unwrap_or_default(),
+            self.usernames,
+            self.logins
+        )
+    }
+}
+
+/// Rows of the analysed ips, the ips with the most logs first
+fn export_rows(app: &App) -> Vec<ExportRow> {
+    let format_timestamp = |micros: Option<i64>| {
+        micros
+            .and_then(|micros| DateTime::from_timestamp(micros.div_euclid(1_000_000), 0))
+            .map(|timestamp: DateTime<Utc>| timestamp.to_rfc3339())
+    };
+    app.ssh_logs
+        .iter()
+        .map(|(ip, _, logs)| {
+            let stats = app.row_stats.get(ip).cloned().unwrap_or_default();
+            ExportRow {
+                ip: *ip,
+                logs: *logs,
+                attempts: stats.attempts,
+                first_seen: format_timestamp(stats.first_seen),
+                last_seen: format_timestamp(stats.last_seen),
+                usernames: stats.usernames,
+                logins: stats.logins,
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn to_csv(rows: &[ExportRow]) -> String {
+    let mut csv = String::from(ExportRow::CSV_HEADER);
+    csv.push('\n');
+    for row in rows {
+        csv.push_str(&row.to_csv());
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Writes the logs per ip to the file, or to stdout without one
+pub(crate) fn export(
+    app: &App,
+    format: ExportFormat,
+    output: Option<&Path>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let rows = export_rows(app);
+    let content = match format {
+        ExportFormat::Csv => to_csv(&rows),
+        ExportFormat::Json => serde_json::to_string_pretty(&rows)? + "\n",
+    };
+    match output {
+        Some(path) => fs::write(path, content)?,
+        None => io::stdout().write_all(content.as_bytes())?,
+    }
+    Ok(())
+}
+
+/// Prints the figures of the dashboard with the top ips and usernames
+pub(crate) async fn report(app: &App, crawler: &Crawler, top: usize) {
+    let summary = &app.log_summary;
+    println!("Logs {}", app.time_range);
+    println!(
+        "{} attempts by {} ips with {} usernames, {} successful logins",
+        summary.attempts, summary.ips, summary.usernames, summary.logins
+    );
+    println!("Peak rate: {} attempts per minute", summary.peak_rate());
+    println!();
+    println!("Top ips:");
+    for (ip, _, logs) in app.ssh_logs.iter().take(top) {
+        // Only saved locations are shown, the report does not look up ips
+        let country = crawler
+            .geolocation_history(ip)
+            .await
+            .ok()
+            .and_then(|history| history.into_iter().next())
+            .and_then(|location| location.country_name)
+            .unwrap_or_else(|| "unknown".to_owned());
+        println!("  {:<15} {:>6} logs  {}", ip, logs, country);
+    }
+    println!();
+    println!("Top usernames:");
+    for (username, attempts) in summary.top_usernames.iter().take(top) {
+        println!("  {:<15} {:>6} attempts", username, attempts);
+    }
+}
+
+/// Geolocates the ip and prints its saved location
+pub(crate) async fn lookup(crawler: &Crawler, ip: &Ipv4Addr) -> Result<(), CrawlerError> {
+    crawler.geolocation(ip).await?;
+    let Some(location) = crawler.geolocation_history(ip).await?.into_iter().next() else {
+        println!("No location known for {}", ip);
+        return Ok(());
+    };
+    println!("{}", ip);
+    println!("  Location: {}, {}", location.latitude, location.longitude);
+    println!(
+        "  Country:  {}",
+        location.country_name.as_deref().unwrap_or("unknown")
+    );
+    println!(
+        "  Org:      {}",
+        location.org.as_deref().unwrap_or("unknown")
+    );
+    println!(
+        "  Asn:      {}",
+        location.asn.as_deref().unwrap_or("unknown")
+    );
+    println!("  Fetched:  {}", location.created);
+    Ok(())
+}
+
+pub(crate) async fn print_stats(crawler: &Crawler) -> Result<(), CrawlerError> {
+    let stats = crawler.stats().await?;
+    println!("Ips:               {}", stats.ips);
+    println!("Geolocations:      {}", stats.geolocations);
+    println!("Events:            {}", stats.events);
+    println!("Daily activities:  {}", stats.daily_activities);
+    println!("Annotations:       {}", stats.annotations);
+    println!("Bans:              {}", stats.bans);
+    println!("Lookup failures:   {}", stats.lookup_failures);
+    Ok(())
+}
+
+pub(crate) async fn prune(crawler: &Crawler) -> Result<(), CrawlerError> {
+    let report = crawler.prune().await?;
+    println!(
+        "Removed {} events, {} daily activities and {} geolocations",
+        report.events, report.daily_activities, report.geolocations
+    );
+    Ok(())
+}
+
+/// Writes the default config, an existing config is only replaced with `force`
+pub(crate) fn init_config(path: &Path, force: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let existing = fs::read_to_string(path).unwrap_or_default();
+    if !existing.trim().is_empty() && !force {
+        return Err(format!("{} already exists, replace it with --force", path.display()).into());
+    }
+    if let Some(config_dir) = path.parent() {
+        fs::create_dir_all(config_dir)?;
+    }
+    fs::write(path, DEFAULT_CONFIG)?;
+    println!("Wrote {}", path.display());
+    Ok(())
+}
+
+/// Loads the config and the lists it refers to
+pub(crate) fn check_config(path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let settings = Settings::load(path)?;
+    settings
+        .allowlist
+        .parse()
+        .map_err(|err| format!("Invalid allowlist: {}", err))?;
+    for list in &settings.crawler.blocklists {
+        Blocklist::from_file(&list.name, &list.path)
+            .map_err(|err| format!("Blocklist {}: {}", list.path.display(), err))?;
+    }
+    println!("{} is valid", path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_write_csv_rows() {
+        let rows = [ExportRow {
+            ip: Ipv4Addr::new(203, 0, 113, 7),
+            logs: 4,
+            attempts: 3,
+            first_seen: Some("2023-12-24T18:00:00+00:00".to_owned()),
+            last_seen: None,
+            usernames: 2,
+            logins: 1,
+        }];
+        assert_eq!(
+            to_csv(&rows),
+            "ip,logs,attempts,first_seen,last_seen,usernames,logins\n\
+             203.0.113.7,4,3,2023-12-24T18:00:00+00:00,,2,1\n"
+        );
+        assert_eq!(to_csv(&[]).lines().count(), 1);
+    }
+}
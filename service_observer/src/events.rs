@@ -1,5 +1,57 @@
+use crate::app::blocking::BlockTarget;
+use crawler::{
+    ActivityRecord, CrawlerError, Enrichment, GeolocationRecord, IpScannerError, LookupResult,
+    Progress, Reputation,
+};
+use ip_blocking::Fail2banBan;
+use journal_parser::models::LogEntry;
+use std::net::Ipv4Addr;
+
 #[derive(Debug)]
 pub(crate) enum ObserverEvents {
-    /// First eintry is lat, second is lng
-    Geolocation((f64, f64)),
+    /// Latest saved geolocation of the ip
+    Geolocation((Ipv4Addr, GeolocationRecord)),
+    /// The geolocation provider has no data for the ip
+    UnknownLocation(Ipv4Addr),
+    Reputation((Ipv4Addr, Reputation)),
+    Enrichment((Ipv4Addr, Enrichment)),
+    LookupProgress(Progress),
+    /// Active bans of the local fail2ban, replaces the previously read ones
+    Fail2banBans(Vec<Fail2banBan>),
+    /// Saved data of the ip opened in the detail pane
+    IpDetails((Ipv4Addr, Option<GeolocationRecord>, Option<ActivityRecord>)),
+    /// Entry written to the followed journal after the start
+    NewLogEntry(Box<LogEntry>),
+    /// Outcome of the confirmed block preview, applied in the background
+    BlockApplied((BlockTarget, Vec<Ipv4Addr>, Result<(), String>)),
+    /// Failed background action, shown in the status bar
+    Error(String),
+}
+
+impl ObserverEvents {
+    pub fn from_lookup(lookup: LookupResult) -> Vec<Self> {
+        let mut events = vec![];
+        match lookup.geolocation {
+            Ok(_) => {
+                if let Some(location) = lookup.location {
+                    events.push(Self::Geolocation((lookup.ipv4, location)));
+                }
+            }
+            // Failed requests and backoffs are retried, only a missing answer is final
+            Err(CrawlerError::ScannerInteraction(IpScannerError::NoData(_))) => {
+                events.push(Self::UnknownLocation(lookup.ipv4))
+            }
+            Err(_) => {
+                // TODO: For now ignore errors, this should be logged somewhere
+            }
+        }
+        if let Ok(reputation) = lookup.reputation {
+            events.push(Self::Reputation((lookup.ipv4, reputation)));
+        }
+        if let Ok(enrichment) = lookup.enrichment {
+            events.push(Self::Enrichment((lookup.ipv4, enrichment)));
+        }
+        events.push(Self::LookupProgress(lookup.progress));
+        events
+    }
 }
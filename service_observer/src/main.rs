@@ -1,16 +1,29 @@
-use app::config::get_db_path;
+use app::args::{Args, Command, ConfigCommand, DbCommand};
+use app::blocking::BlockTarget;
+use app::config::{
+    create_config, create_database, get_config_path, save_allowlist, Blocking, Fail2ban, Settings,
+};
+use app::map::{MapPreset, MapView};
 use app::{constants, App};
+use clap::Parser;
 use clokwerk::{AsyncScheduler, TimeUnits};
-use crawler::{AppCrawler, Crawler};
+use crawler::{
+    Allowlist, AppCrawler, Blocklist, Crawler, EnrichmentScanner, ReputationScanner, WorkQueue,
+};
 use crossterm::event::{self, Event, KeyCode};
 use events::ObserverEvents;
+use ip_blocking::{Action, BlockBackend, Fail2banBan, Fail2banDb, IpBlockingError};
+use journal_parser::journal::{FollowHandle, Journal};
 use ratatui::backend::Backend;
 use ratatui::{backend::CrosstermBackend, Terminal};
-use std::sync::mpsc::{self, Receiver};
+use std::collections::HashMap;
+use std::net::Ipv4Addr;
+use std::sync::mpsc::{self, Receiver, SyncSender};
 use std::sync::Arc;
 use std::time::Duration;
 
 mod app;
+mod commands;
 mod events;
 mod ui;
 mod util;
@@ -19,6 +32,9 @@ fn run_app<B: Backend>(
     terminal: &mut Terminal<B>,
     mut app: App,
     event_recevier: Receiver<ObserverEvents>,
+    event_sender: SyncSender<ObserverEvents>,
+    crawler: Arc<Crawler>,
+    queue: Arc<WorkQueue>,
 ) -> std::io::Result<()> {
     loop {
         if let Ok(event_received) =
@@ -26,13 +42,177 @@ fn run_app<B: Backend>(
         {
             if event_received {
                 if let Event::Key(key) = event::read()? {
-                    match key.code {
-                        KeyCode::Char('q') | KeyCode::Esc => break,
-                        KeyCode::Left => app.previous_tab(),
-                        KeyCode::Right => app.next_tab(),
-                        KeyCode::Down => app.down_row(),
-                        KeyCode::Up => app.up_row(),
-                        _ => {}
+                    app.status_error = None;
+                    if let Some(editor) = &mut app.annotation_editor {
+                        match key.code {
+                            KeyCode::Esc => app.close_annotation_editor(),
+                            KeyCode::Enter => {
+                                if let Some((ip, annotation)) = app.finish_annotation_editor() {
+                                    let crawler = crawler.clone();
+                                    let sender = event_sender.clone();
+                                    tokio::spawn(async move {
+                                        if let Err(err) =
+                                            crawler.save_annotation(&ip, annotation).await
+                                        {
+                                            let error = format!(
+                                                "Failed to save the annotation of {}: {}",
+                                                ip, err
+                                            );
+                                            send_error(sender, error).await;
+                                        }
+                                    });
+                                }
+                            }
+                            KeyCode::Tab => editor.next_field(),
+                            KeyCode::Backspace => editor.pop(),
+                            KeyCode::Char(character) => editor.push(character),
+                            _ => {}
+                        }
+                    } else if let Some(picker) = &mut app.time_range_picker {
+                        match key.code {
+                            KeyCode::Esc => app.close_time_range_picker(),
+                            KeyCode::Enter => {
+                                let reloaded = app.confirm_time_range_picker();
+                                if reloaded {
+                                    queue.extend(app.ssh_logs.iter().map(|(ip, _, _)| *ip));
+                                    let events = app.ssh_events();
+                                    let crawler = crawler.clone();
+                                    let sender = event_sender.clone();
+                                    tokio::spawn(async move {
+                                        if let Err(err) = crawler.ingest_events(&events).await {
+                                            let error =
+                                                format!("Failed to save the ssh events: {}", err);
+                                            send_error(sender, error).await;
+                                        }
+                                    });
+                                }
+                            }
+                            KeyCode::Down => picker.next(),
+                            KeyCode::Up => picker.previous(),
+                            KeyCode::Tab => picker.next_field(),
+                            KeyCode::Backspace => picker.pop(),
+                            KeyCode::Char(character) => picker.push(character),
+                            _ => {}
+                        }
+                    } else if app.editing_search {
+                        match key.code {
+                            KeyCode::Esc => app.clear_search(),
+                            KeyCode::Enter => app.finish_search(),
+                            KeyCode::Backspace => app.pop_search(),
+                            KeyCode::Char(character) => app.push_search(character),
+                            _ => {}
+                        }
+                    } else if app.detail.is_some() {
+                        match key.code {
+                            KeyCode::Char('q') | KeyCode::Esc | KeyCode::Enter => {
+                                app.close_detail()
+                            }
+                            KeyCode::Down => app.scroll_detail_down(false),
+                            KeyCode::Up => app.scroll_detail_up(false),
+                            KeyCode::PageDown => app.scroll_detail_down(true),
+                            KeyCode::PageUp => app.scroll_detail_up(true),
+                            _ => {}
+                        }
+                    } else if app.block_preview.is_some() {
+                        match key.code {
+                            KeyCode::Char('q') | KeyCode::Esc => app.close_block_preview(),
+                            KeyCode::Enter => {
+                                if let Some((target, ips, actions)) = app.start_block_preview() {
+                                    let crawler = crawler.clone();
+                                    let sender = event_sender.clone();
+                                    tokio::spawn(apply_block(
+                                        crawler, target, ips, actions, sender,
+                                    ));
+                                }
+                            }
+                            _ => {}
+                        }
+                    } else {
+                        match key.code {
+                            KeyCode::Esc if app.active_search().is_some() => app.clear_search(),
+                            KeyCode::Char('q') | KeyCode::Esc => break,
+                            KeyCode::Left => app.previous_tab(),
+                            KeyCode::Right => app.next_tab(),
+                            KeyCode::Down => app.down_row(),
+                            KeyCode::Up => app.up_row(),
+                            KeyCode::PageDown => app.page_down(),
+                            KeyCode::PageUp => app.page_up(),
+                            KeyCode::Home => app.first_row(),
+                            KeyCode::End => app.last_row(),
+                            KeyCode::Char('s') => app.next_sort_column(),
+                            KeyCode::Char('S') => app.reverse_sort(),
+                            KeyCode::Char('a') => app.open_annotation_editor(),
+                            KeyCode::Char('t') => app.next_tag_filter(),
+                            KeyCode::Char('/') => app.open_search(),
+                            KeyCode::Enter if app.on_map_tab() => app.show_marker_in_table(),
+                            KeyCode::Char('m') => app.show_selected_on_map(),
+                            KeyCode::Char('r') => app.open_time_range_picker(),
+                            KeyCode::Char('+') if app.on_map_tab() => app.map_view.zoom_in(),
+                            KeyCode::Char('-') if app.on_map_tab() => app.map_view.zoom_out(),
+                            KeyCode::Char('h') if app.on_map_tab() => app.map_view.pan(-1.0, 0.0),
+                            KeyCode::Char('l') if app.on_map_tab() => app.map_view.pan(1.0, 0.0),
+                            KeyCode::Char('k') if app.on_map_tab() => app.map_view.pan(0.0, 1.0),
+                            KeyCode::Char('j') if app.on_map_tab() => app.map_view.pan(0.0, -1.0),
+                            KeyCode::Char('0') if app.on_map_tab() => {
+                                app.map_view = MapView::from_preset(MapPreset::World)
+                            }
+                            KeyCode::Char('1') if app.on_map_tab() => {
+                                app.map_view = MapView::from_preset(MapPreset::Europe)
+                            }
+                            KeyCode::Char('2') if app.on_map_tab() => {
+                                app.map_view = MapView::from_preset(MapPreset::NorthAmerica)
+                            }
+                            KeyCode::Char('3') if app.on_map_tab() => {
+                                app.map_view = MapView::from_preset(MapPreset::Asia)
+                            }
+                            KeyCode::Char('+') if app.on_timeline_tab() => {
+                                app.larger_timeline_buckets()
+                            }
+                            KeyCode::Char('-') if app.on_timeline_tab() => {
+                                app.smaller_timeline_buckets()
+                            }
+                            KeyCode::Char('W') => app.toggle_allowlisted(),
+                            KeyCode::Enter => {
+                                if let Some(ip) = app.open_detail() {
+                                    let crawler = crawler.clone();
+                                    let sender = event_sender.clone();
+                                    tokio::spawn(async move {
+                                        let geolocation = crawler
+                                            .geolocation_history(&ip)
+                                            .await
+                                            .ok()
+                                            .and_then(|history| history.into_iter().next());
+                                        let activity = crawler.activity(&ip).await.ok().flatten();
+                                        let details =
+                                            ObserverEvents::IpDetails((ip, geolocation, activity));
+                                        // The app was closed if the send fails
+                                        let _ = tokio::task::spawn_blocking(move || {
+                                            sender.send(details).is_ok()
+                                        })
+                                        .await;
+                                    });
+                                }
+                            }
+                            KeyCode::Char('b') => app.open_block_preview(),
+                            KeyCode::Char('f') => app.open_fail2ban_ban_preview(),
+                            KeyCode::Char('u') => app.open_fail2ban_unban_preview(),
+                            KeyCode::Char('w') => {
+                                if let Some((ip, allowlisted)) = app.allowlist_selected() {
+                                    crawler.set_allowlist(app.allowlist.clone());
+                                    if let Err(err) = save_allowlist(
+                                        &app.settings.path,
+                                        &app.allowlist.networks(),
+                                    ) {
+                                        app.status_error =
+                                            Some(format!("Failed to save the allowlist: {}", err));
+                                    }
+                                    if !allowlisted {
+                                        queue.push(ip);
+                                    }
+                                }
+                            }
+                            _ => {}
+                        }
                     }
                 }
             }
@@ -41,63 +221,374 @@ fn run_app<B: Backend>(
             event_recevier.recv_timeout(Duration::from_millis(constants::APP_DRAW_TICK_RATE / 2))
         {
             match input_event {
-                ObserverEvents::Geolocation((lat, lng)) => app.add_geolocation(lat, lng),
+                ObserverEvents::Geolocation((ip, location)) => app.add_geolocation(ip, location),
+                ObserverEvents::UnknownLocation(ip) => app.add_unknown_location(ip),
+                ObserverEvents::Reputation((ip, reputation)) => app.add_reputation(ip, reputation),
+                ObserverEvents::Enrichment((ip, enrichment)) => app.add_enrichment(ip, enrichment),
+                ObserverEvents::LookupProgress(progress) => app.lookup_progress = progress,
+                ObserverEvents::BlockApplied((target, ips, status)) => {
+                    app.finish_block_preview(target, ips, status)
+                }
+                ObserverEvents::Error(error) => app.status_error = Some(error),
+                ObserverEvents::Fail2banBans(bans) => app.set_fail2ban_bans(bans),
+                ObserverEvents::IpDetails((ip, geolocation, activity)) => {
+                    app.set_ip_details(ip, geolocation, activity)
+                }
+                ObserverEvents::NewLogEntry(entry) => {
+                    if let Some(event) = app.add_log_entry(*entry) {
+                        // Known ips are only looked up once by the queue
+                        if !app.allowlist.contains(&event.ipv4) {
+                            queue.push(event.ipv4);
+                        }
+                        let crawler = crawler.clone();
+                        let sender = event_sender.clone();
+                        tokio::spawn(async move {
+                            if let Err(err) = crawler.ingest_events(&[event]).await {
+                                let error = format!("Failed to save the ssh event: {}", err);
+                                send_error(sender, error).await;
+                            }
+                        });
+                    }
+                }
             }
         }
+        app.refresh_log_stats();
         terminal.draw(|frame| ui::ui(frame, &mut app))?;
     }
     Ok(())
 }
 
+/// Runs the actions off the ui thread, the commands of some backends take a while per ip
+async fn apply_block(
+    crawler: Arc<Crawler>,
+    target: BlockTarget,
+    ips: Vec<Ipv4Addr>,
+    actions: Vec<Action>,
+    sender: SyncSender<ObserverEvents>,
+) {
+    let status = tokio::task::spawn_blocking(move || {
+        actions
+            .iter()
+            .try_for_each(Action::apply)
+            .map_err(|err| err.to_string())
+    })
+    .await
+    .unwrap_or_else(|err| Err(err.to_string()));
+    if let (Ok(()), BlockTarget::Firewall { backend, duration }) = (&status, &target) {
+        if let Err(err) = crawler.save_bans(&ips, backend.as_str(), *duration).await {
+            let error = format!("Failed to save the bans: {}", err);
+            send_error(sender.clone(), error).await;
+        }
+    }
+    let applied = ObserverEvents::BlockApplied((target, ips, status));
+    // The app was closed if the send fails
+    let _ = tokio::task::spawn_blocking(move || sender.send(applied).is_ok()).await;
+}
+
+/// Shows the error in the status bar of the app
+async fn send_error(sender: SyncSender<ObserverEvents>, error: String) {
+    // The app was closed if the send fails
+    let _ = tokio::task::spawn_blocking(move || sender.send(ObserverEvents::Error(error)).is_ok())
+        .await;
+}
+
+/// Sends the entries of the units written to the journal from now on, until the app is closed
+/// or the returned handle stops the journal
+fn follow_journal(
+    units: &[String],
+    cursor: Option<&str>,
+    sender: SyncSender<ObserverEvents>,
+) -> Option<FollowHandle> {
+    let units: Vec<&str> = units.iter().map(String::as_str).collect();
+    let journal = Journal::with_services(&units).no_pager();
+    let journal = match cursor {
+        Some(cursor) => journal.after_cursor(cursor),
+        None => journal.lines(0),
+    };
+    let entries = journal.build().follow().ok()?;
+    let handle = entries.handle();
+    std::thread::spawn(move || {
+        // Unparsable entries are skipped like in the initial read
+        for entry in entries.flatten() {
+            if sender
+                .send(ObserverEvents::NewLogEntry(Box::new(entry)))
+                .is_err()
+            {
+                return;
+            }
+        }
+    });
+    Some(handle)
+}
+
+/// Exits the process if a blocklist is unreadable, the enrichment settings are invalid or the
+/// database is not reachable
+async fn setup_crawler(settings: &Settings, database_url: &str, allowlist: Allowlist) -> Crawler {
+    let mut blocklists = Vec::with_capacity(settings.crawler.blocklists.len());
+    for list in &settings.crawler.blocklists {
+        match Blocklist::from_file(&list.name, &list.path) {
+            Ok(blocklist) => blocklists.push(blocklist),
+            Err(err) => {
+                eprintln!("Failed to read blocklist {}: {}", list.path.display(), err);
+                std::process::exit(1);
+            }
+        }
+    }
+    let reputation_scanner =
+        ReputationScanner::new(settings.crawler.abuseipdb_token.as_deref(), blocklists);
+    let enrichment_scanner = match EnrichmentScanner::new(
+        settings.crawler.nameserver,
+        settings.crawler.rdap_url.as_deref(),
+    ) {
+        Ok(enrichment_scanner) => enrichment_scanner,
+        Err(err) => {
+            eprintln!("Failed to set up the enrichment scanner: {:?}", err);
+            std::process::exit(1);
+        }
+    };
+    let crawler = match Crawler::new(database_url, &settings.crawler.shodan_token).await {
+        Ok(crawler) => crawler,
+        Err(err) => {
+            eprintln!("Failed to set up the crawler: {:?}", err);
+            std::process::exit(1);
+        }
+    };
+    crawler
+        .with_reputation_scanner(reputation_scanner)
+        .with_enrichment_scanner(enrichment_scanner)
+        .with_geolocation_ttl(Duration::from_secs(
+            settings.crawler.geolocation_ttl_days * 24 * 60 * 60,
+        ))
+        .with_reputation_ttl(Duration::from_secs(
+            settings.crawler.reputation_ttl_days * 24 * 60 * 60,
+        ))
+        .with_retention_policy(settings.retention)
+        .with_allowlist(allowlist)
+}
+
+/// Active bans of the local fail2ban, none if it is disabled
+async fn read_fail2ban_bans(fail2ban: &Fail2ban) -> Result<Vec<Fail2banBan>, IpBlockingError> {
+    if !fail2ban.enabled {
+        return Ok(Vec::new());
+    }
+    Fail2banDb::open(&fail2ban.database_path)
+        .await?
+        .active_bans()
+        .await
+}
+
+/// Lifts bans which expired, failed lifts are repeated with the next run
+async fn lift_expired_bans(crawler: &Crawler, blocking: &Blocking) {
+    let Ok(bans) = crawler.bans().await else {
+        return;
+    };
+    let mut expired: HashMap<BlockBackend, Vec<Ipv4Addr>> = HashMap::new();
+    for (ip, ban) in bans.into_iter().filter(|(_, ban)| ban.is_expired()) {
+        // Bans of unknown backends can't be lifted and are only forgotten
+        let backend = ban.backend.parse().unwrap_or_default();
+        expired.entry(backend).or_default().push(ip);
+    }
+    for (backend, ips) in expired {
+        if !backend.expires_natively() {
+            let firewall = blocking.firewall(backend);
+            if firewall.apply(&firewall.unban_actions(&ips)).is_err() {
+                continue;
+            }
+        }
+        let _ = crawler.delete_bans(&ips).await;
+    }
+}
+
+/// Prints the error and exits the process if the command failed
+fn exit_on_error<T, E: std::fmt::Display>(result: Result<T, E>, action: &str) -> T {
+    match result {
+        Ok(value) => value,
+        Err(err) => {
+            eprintln!("Failed to {}: {}", action, err);
+            std::process::exit(1);
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() {
-    let stdout = util::setup_terminal().unwrap();
+    let args = Args::parse();
+    let command = args.command.clone().unwrap_or(Command::Tui);
+    let config_path = args.config.clone().unwrap_or_else(get_config_path);
+    // The config commands work without a valid config
+    if let Command::Config { command } = &command {
+        match command {
+            ConfigCommand::Init { force } => exit_on_error(
+                commands::init_config(&config_path, *force),
+                "write the config",
+            ),
+            ConfigCommand::Check => {
+                exit_on_error(commands::check_config(&config_path), "check the config")
+            }
+        }
+        return;
+    }
+    create_config(&config_path);
+    let settings = match Settings::load(&config_path) {
+        Ok(settings) => settings,
+        Err(err) => {
+            eprintln!("Failed to read {}: {}", config_path.display(), err);
+            std::process::exit(1);
+        }
+    };
+    let allowlist = match settings.allowlist.parse() {
+        Ok(allowlist) => allowlist,
+        Err(err) => {
+            eprintln!("Invalid allowlist in {}: {}", config_path.display(), err);
+            std::process::exit(1);
+        }
+    };
+    // The default sqlite file is only created if no other database is configured
+    if args.database.is_none() && settings.crawler.database_url.is_none() {
+        create_database();
+    }
+    let database_url = settings.database_url(args.database.as_deref());
+    let crawler = setup_crawler(&settings, &database_url, allowlist.clone()).await;
+    let load_app = || exit_on_error(App::new(args, settings, allowlist), "read the sshd logs");
+    match command {
+        Command::Tui => run_tui(load_app(), crawler).await,
+        Command::Report { top } => {
+            let app = load_app();
+            commands::report(&app, &crawler, top).await;
+        }
+        Command::Export { format, output } => {
+            let app = load_app();
+            exit_on_error(
+                commands::export(&app, format, output.as_deref()),
+                "export the logs",
+            );
+        }
+        Command::Lookup { ip } => {
+            exit_on_error(commands::lookup(&crawler, &ip).await, "look up the ip")
+        }
+        // Migrations are applied while connecting to the database
+        Command::Db {
+            command: DbCommand::Migrate,
+        } => println!("The database is up to date"),
+        Command::Db {
+            command: DbCommand::Prune,
+        } => exit_on_error(commands::prune(&crawler).await, "prune the database"),
+        Command::Db {
+            command: DbCommand::Stats,
+        } => exit_on_error(
+            commands::print_stats(&crawler).await,
+            "read the database stats",
+        ),
+        Command::Config { .. } => unreachable!("Handled before reading the config"),
+    }
+}
 
+async fn run_tui(mut app: App, crawler: Crawler) {
     let mut scheduler = AsyncScheduler::new();
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend).unwrap();
-
     let (sender, receiver) = mpsc::sync_channel::<ObserverEvents>(1);
 
-    // Create task runner thread
-    let sender_arc = Arc::new(sender);
-
-    // Create App and run
-    let app = App::default();
-    let crawler = Arc::new(
-        Crawler::new(
-            &get_db_path().into_os_string().into_string().unwrap(),
-            &app.settings.crawler.shodan_token,
-        )
-        .await,
-    );
-    let ssh_logs = Arc::new(app.mut_ssh_logs());
-    scheduler.every(1.seconds()).run(move || {
-        let ssh_logs = ssh_logs.clone();
-        let thread_sender = sender_arc.clone();
-        let thread_crawler = crawler.clone();
+    app.set_annotations(crawler.annotations().await.unwrap_or_default());
+    app.banned = crawler
+        .bans()
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|(_, ban)| !ban.is_expired())
+        .map(|(ip, _)| ip)
+        .collect();
+    // Keep the history of the read logs beyond the journal rotation
+    if let Err(err) = crawler.ingest_events(&app.ssh_events()).await {
+        eprintln!("Failed to save the ssh events: {:?}", err);
+    }
+    let crawler = Arc::new(crawler);
+    let prune_crawler = crawler.clone();
+    scheduler.every(1.day()).run(move || {
+        let crawler = prune_crawler.clone();
+        async move {
+            // Failed prunes are repeated with the next run
+            let _ = crawler.prune().await;
+        }
+    });
+    let ban_crawler = crawler.clone();
+    let blocking = Arc::new(app.settings.blocking.clone());
+    scheduler.every(10.minutes()).run(move || {
+        let crawler = ban_crawler.clone();
+        let blocking = blocking.clone();
+        async move {
+            lift_expired_bans(&crawler, &blocking).await;
+        }
+    });
+    match read_fail2ban_bans(&app.settings.fail2ban).await {
+        Ok(bans) => app.set_fail2ban_bans(bans),
+        Err(err) => app.status_error = Some(format!("Failed to read the fail2ban bans: {}", err)),
+    }
+    let fail2ban = Arc::new(app.settings.fail2ban.clone());
+    let fail2ban_sender = sender.clone();
+    let detail_sender = sender.clone();
+    scheduler.every(1.minute()).run(move || {
+        let fail2ban = fail2ban.clone();
+        let sender = fail2ban_sender.clone();
+        async move {
+            let bans = match read_fail2ban_bans(&fail2ban).await {
+                Ok(bans) => bans,
+                Err(err) => {
+                    let error = format!("Failed to read the fail2ban bans: {}", err);
+                    send_error(sender, error).await;
+                    return;
+                }
+            };
+            // The app was closed if the send fails
+            let _ = tokio::task::spawn_blocking(move || {
+                sender.send(ObserverEvents::Fail2banBans(bans)).is_ok()
+            })
+            .await;
+        }
+    });
+    // Look up every ip once, failed lookups are retried once their backoff expired
+    let (queue, mut lookup_results) =
+        WorkQueue::spawn(crawler.clone(), app.settings.crawler.concurrency);
+    queue.extend(app.ssh_logs.iter().map(|(ip, _, _)| *ip));
+    let queue = Arc::new(queue);
+    let retry_queue = queue.clone();
+    scheduler.every(5.minutes()).run(move || {
+        let queue = retry_queue.clone();
         async move {
-            for (ip, _, _) in ssh_logs.iter() {
-                if let Ok((lat, lng)) = thread_crawler.geolocation(ip).await {
-                    thread_sender
-                        .send(ObserverEvents::Geolocation((lat, lng)))
-                        .unwrap();
-                } else {
-                    // TODO: For now ignore errors, this should be logged somewhere
+            queue.retry_failed();
+        }
+    });
+    tokio::task::spawn_blocking(move || {
+        while let Some(lookup) = lookup_results.blocking_recv() {
+            for event in ObserverEvents::from_lookup(lookup) {
+                if sender.send(event).is_err() {
+                    // App was closed
+                    return;
                 }
             }
         }
     });
+    let journal_follow = follow_journal(
+        &app.units,
+        app.journal_cursor.as_deref(),
+        detail_sender.clone(),
+    );
+    let stdout = util::setup_terminal().unwrap();
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend).unwrap();
+
     let task_handler = tokio::spawn(async move {
         loop {
             scheduler.run_pending().await;
             tokio::time::sleep(Duration::from_millis(constants::TASK_TICK_RATE_MS)).await;
         }
     });
-    let res = run_app(&mut terminal, app, receiver);
+    let res = run_app(&mut terminal, app, receiver, detail_sender, crawler, queue);
 
     util::cleanup_terminal(&mut terminal).unwrap();
     task_handler.abort();
+    // The journalctl child would outlive the app while it waits for new entries
+    if let Some(journal_follow) = journal_follow {
+        journal_follow.stop();
+    }
 
     // Print errors during exec
     if let Err(err) = res {
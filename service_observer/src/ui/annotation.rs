@@ -0,0 +1,82 @@
+use ratatui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::{
+    editor::{AnnotationEditor, AnnotationField},
+    App,
+};
+
+pub(crate) fn annotation_popup<B: Backend>(
+    frame: &mut Frame<B>,
+    editor: &AnnotationEditor,
+    app: &App,
+) {
+    let area = centered_rect(60, 40, frame.size());
+    let block = Block::default()
+        .title(format!(
+            "Annotate {} - Tab: switch field, Enter: save, Esc: cancel",
+            editor.ip
+        ))
+        .borders(Borders::ALL);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([Constraint::Length(3), Constraint::Min(3)].as_ref())
+        .split(area);
+    let field_style = |field: AnnotationField| {
+        if editor.field == field {
+            Style::default().fg(app.settings.ui.accent_color)
+        } else {
+            Style::default().fg(app.settings.ui.primary_color)
+        }
+    };
+    let tags = Paragraph::new(editor.tags.as_str()).block(
+        Block::default()
+            .title("Tags (comma separated)")
+            .borders(Borders::ALL)
+            .border_style(field_style(AnnotationField::Tags)),
+    );
+    let note = Paragraph::new(editor.note.as_str())
+        .wrap(Wrap { trim: false })
+        .block(
+            Block::default()
+                .title("Note")
+                .borders(Borders::ALL)
+                .border_style(field_style(AnnotationField::Note)),
+        );
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+    frame.render_widget(tags, chunks[0]);
+    frame.render_widget(note, chunks[1]);
+}
+
+/// Rect of the given percentage size in the middle of the area
+pub(super) fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Percentage((100 - percent_y) / 2),
+                Constraint::Percentage(percent_y),
+                Constraint::Percentage((100 - percent_y) / 2),
+            ]
+            .as_ref(),
+        )
+        .split(area);
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(
+            [
+                Constraint::Percentage((100 - percent_x) / 2),
+                Constraint::Percentage(percent_x),
+                Constraint::Percentage((100 - percent_x) / 2),
+            ]
+            .as_ref(),
+        )
+        .split(vertical[1])[1]
+}
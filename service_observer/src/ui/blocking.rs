@@ -0,0 +1,40 @@
+use ratatui::{
+    backend::Backend,
+    style::Style,
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use super::annotation::centered_rect;
+use crate::app::{blocking::BlockPreview, App};
+
+pub(crate) fn block_popup<B: Backend>(frame: &mut Frame<B>, preview: &BlockPreview, app: &App) {
+    let area = centered_rect(80, 60, frame.size());
+    let title = match &preview.status {
+        None if preview.applying => format!("Applying: {}", preview.description()),
+        None => format!(
+            "Dry run: {} - Enter: apply, Esc: cancel",
+            preview.description()
+        ),
+        Some(Ok(())) => format!("Applied: {} - Esc: close", preview.description()),
+        Some(Err(_)) => format!("Failed: {} - Esc: close", preview.description()),
+    };
+    let mut lines: Vec<Line> = Vec::new();
+    if let Some(Err(err)) = &preview.status {
+        lines.push(Line::styled(
+            err.to_owned(),
+            Style::default().fg(app.settings.ui.accent_color),
+        ));
+        lines.push(Line::from(""));
+    }
+    if preview.ips.is_empty() {
+        lines.push(Line::from("No ips selected"));
+    }
+    lines.extend(preview.lines().into_iter().map(Line::from));
+    let commands = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .block(Block::default().title(title).borders(Borders::ALL));
+    frame.render_widget(Clear, area);
+    frame.render_widget(commands, area);
+}
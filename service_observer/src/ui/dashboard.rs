@@ -0,0 +1,154 @@
+use chrono::Utc;
+use ratatui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Bar, BarChart, BarGroup, Block, Borders, Gauge, List, ListItem, Paragraph},
+    Frame,
+};
+
+use crate::app::{dashboard::top_counts, App};
+
+/// Minutes the current attack rate is averaged over
+const RATE_MINUTES: i64 = 5;
+
+/// Summary of the analysed logs and lookups
+pub(crate) fn dashboard<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Length(3),
+                Constraint::Length(3),
+                Constraint::Min(5),
+            ]
+            .as_ref(),
+        )
+        .split(area);
+    let label_style = Style::default()
+        .fg(app.settings.ui.accent_color)
+        .add_modifier(Modifier::BOLD);
+    let summary = &app.log_summary;
+    let figures = [
+        ("Attempts: ", summary.attempts),
+        ("  Unique IPs: ", summary.ips),
+        ("  Unique usernames: ", summary.usernames),
+        ("  Successful logins: ", summary.logins),
+    ];
+    let figures = Paragraph::new(Line::from(
+        figures
+            .into_iter()
+            .flat_map(|(label, figure)| {
+                [
+                    Span::styled(label, label_style),
+                    Span::raw(figure.to_string()),
+                ]
+            })
+            .collect::<Vec<_>>(),
+    ))
+    .block(Block::default().title("Summary").borders(Borders::ALL));
+    frame.render_widget(figures, rows[0]);
+
+    let gauges = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+        .split(rows[1]);
+    let gauge_style = Style::default()
+        .fg(app.settings.ui.accent_color)
+        .bg(app.settings.ui.secondary_color);
+    let rate = summary.attack_rate(Utc::now().timestamp_micros(), RATE_MINUTES);
+    let peak = summary.peak_rate();
+    let rate_gauge = Gauge::default()
+        .block(
+            Block::default()
+                .title(format!("Attack rate, last {} minutes", RATE_MINUTES))
+                .borders(Borders::ALL),
+        )
+        .gauge_style(gauge_style)
+        .ratio(ratio(rate, peak as f64))
+        .label(format!("{:.1}/min of peak {}/min", rate, peak));
+    frame.render_widget(rate_gauge, gauges[0]);
+    let progress = &app.lookup_progress;
+    let lookup_gauge = Gauge::default()
+        .block(Block::default().title("Lookups").borders(Borders::ALL))
+        .gauge_style(gauge_style)
+        .ratio(ratio(progress.done as f64, progress.queued as f64))
+        .label(format!(
+            "{}/{} ({} failed)",
+            progress.done, progress.queued, progress.failed
+        ));
+    frame.render_widget(lookup_gauge, gauges[1]);
+
+    let tops = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(
+            [
+                Constraint::Percentage(40),
+                Constraint::Percentage(30),
+                Constraint::Percentage(30),
+            ]
+            .as_ref(),
+        )
+        .split(rows[2]);
+    // Attempts of the ips summed up per country and network owner
+    let located_attempts = || {
+        app.ssh_logs.iter().filter_map(|(ip, _, _)| {
+            let location = app.locations.get(ip)?;
+            let attempts = app.row_stats.get(ip)?.attempts;
+            Some((location, attempts))
+        })
+    };
+    let countries =
+        top_counts(located_attempts().filter_map(|(location, attempts)| {
+            Some((location.country_name.to_owned()?, attempts))
+        }));
+    let networks = top_counts(located_attempts().filter_map(|(location, attempts)| {
+        let network = location.asn.to_owned().or(location.org.to_owned())?;
+        Some((network, attempts))
+    }));
+
+    let bar_width = (tops[0].width.saturating_sub(2) / countries.len().max(1) as u16)
+        .saturating_sub(1)
+        .max(1);
+    let bars: Vec<Bar> = countries
+        .iter()
+        .map(|(country, attempts)| {
+            Bar::default()
+                .value(*attempts as u64)
+                .label(country.as_str().into())
+        })
+        .collect();
+    let country_chart = BarChart::default()
+        .block(
+            Block::default()
+                .title("Top countries by attempts")
+                .borders(Borders::ALL),
+        )
+        .bar_width(bar_width)
+        .bar_style(Style::default().fg(app.settings.ui.accent_color))
+        .data(BarGroup::default().bars(&bars));
+    frame.render_widget(country_chart, tops[0]);
+    frame.render_widget(top_list("Top ASNs by attempts", &networks), tops[1]);
+    frame.render_widget(
+        top_list("Top usernames by attempts", &summary.top_usernames),
+        tops[2],
+    );
+}
+
+fn top_list<'a>(title: &'a str, entries: &'a [(String, usize)]) -> List<'a> {
+    let items: Vec<ListItem> = entries
+        .iter()
+        .map(|(entry, amount)| ListItem::new(format!("{:>6}  {}", amount, entry)))
+        .collect();
+    List::new(items).block(Block::default().title(title).borders(Borders::ALL))
+}
+
+/// Ratio for a gauge, which has to be within 0 and 1
+fn ratio(value: f64, max: f64) -> f64 {
+    if max > 0.0 {
+        (value / max).clamp(0.0, 1.0)
+    } else {
+        0.0
+    }
+}
@@ -0,0 +1,136 @@
+use ratatui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Row, Sparkline, Table},
+    Frame,
+};
+
+use crate::app::{
+    detail::{format_timestamp, DetailLine, DetailView},
+    App,
+};
+
+pub(crate) fn detail<B: Backend>(frame: &mut Frame<B>, area: Rect, detail: &DetailView, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Length(4),
+                Constraint::Length(4),
+                Constraint::Min(3),
+            ]
+            .as_ref(),
+        )
+        .split(area);
+    let logs = app.logs_of(&detail.ip);
+    frame.render_widget(summary(detail, app), chunks[0]);
+
+    // The most recent buckets which fit into the pane
+    let attempts: Vec<u64> = app
+        .ip_timeline(&detail.ip)
+        .iter()
+        .map(|bucket| bucket.failed + bucket.successful)
+        .collect();
+    let shown = attempts
+        .len()
+        .min(usize::from(chunks[1].width.saturating_sub(2)));
+    let sparkline = Sparkline::default()
+        .block(
+            Block::default()
+                .title(format!(
+                    "Authentications per {}",
+                    app.timeline_bucket.as_str()
+                ))
+                .borders(Borders::ALL),
+        )
+        .data(&attempts[attempts.len() - shown..])
+        .style(Style::default().fg(app.settings.ui.accent_color));
+    frame.render_widget(sparkline, chunks[1]);
+
+    let header_style = Style::default().bg(app.settings.ui.secondary_color);
+    let rows: Vec<Row> = logs
+        .iter()
+        .skip(detail.scroll)
+        .map(|log| {
+            let line = DetailLine::from_log(log);
+            Row::new(vec![line.timestamp, line.user, line.kind, line.message])
+        })
+        .collect();
+    let title = format!(
+        "Logs {}-{} of {} - Up/Down/PageUp/PageDown: scroll, Esc: close",
+        (detail.scroll + 1).min(logs.len()),
+        logs.len(),
+        logs.len()
+    );
+    let log_table = Table::new(rows)
+        .header(
+            Row::new(vec!["Time", "User", "Event", "Message"])
+                .style(header_style)
+                .bottom_margin(1),
+        )
+        .block(Block::default().title(title).borders(Borders::ALL))
+        .widths(&[
+            Constraint::Length(19),
+            Constraint::Length(16),
+            Constraint::Length(18),
+            Constraint::Min(20),
+        ]);
+    frame.render_widget(log_table, chunks[2]);
+}
+
+/// Location and activity of the ip
+fn summary<'a>(detail: &'a DetailView, app: &'a App) -> Paragraph<'a> {
+    let label_style = Style::default()
+        .fg(app.settings.ui.accent_color)
+        .add_modifier(Modifier::BOLD);
+    let location = match &detail.geolocation {
+        Some(geolocation) => format!(
+            "{} ({:.2}, {:.2}) {} {}",
+            geolocation
+                .country_name
+                .as_deref()
+                .unwrap_or("unknown country"),
+            geolocation.latitude,
+            geolocation.longitude,
+            geolocation.org.as_deref().unwrap_or_default(),
+            geolocation.asn.as_deref().unwrap_or_default(),
+        ),
+        None if !detail.loaded => "loading...".to_owned(),
+        None => "unknown location".to_owned(),
+    };
+    // Without ingested events the seen range is limited to the read logs
+    let logs = app.logs_of(&detail.ip);
+    let (first_seen, last_seen) = match &detail.activity {
+        Some(activity) => (
+            activity.first_seen.to_string(),
+            activity.last_seen.to_string(),
+        ),
+        None => (
+            logs.first()
+                .map(|log| format_timestamp(&log.realtime_timestamp))
+                .unwrap_or_default(),
+            logs.last()
+                .map(|log| format_timestamp(&log.realtime_timestamp))
+                .unwrap_or_default(),
+        ),
+    };
+    let lines = vec![
+        Line::from(vec![
+            Span::styled("Location: ", label_style),
+            Span::raw(location),
+        ]),
+        Line::from(vec![
+            Span::styled("First seen: ", label_style),
+            Span::raw(first_seen),
+            Span::styled("  Last seen: ", label_style),
+            Span::raw(last_seen),
+        ]),
+    ];
+    Paragraph::new(lines).block(
+        Block::default()
+            .title(format!("Details {}", detail.ip))
+            .borders(Borders::ALL),
+    )
+}
@@ -1,36 +1,131 @@
-use crate::app::App;
+use crate::app::{
+    map::{nearest_marker, MapMarker},
+    App,
+};
 use ratatui::{
     backend::Backend,
-    layout::Rect,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
     widgets::{
-        canvas::{Canvas, Map, MapResolution, Points},
-        Block, Borders,
+        canvas::{Canvas, Circle, Map, MapResolution, Points},
+        Block, Borders, Paragraph,
     },
     Frame,
 };
 
+/// Ips listed in the tooltip of a marker
+const TOOLTIP_IPS: usize = 10;
+
 pub(crate) fn map<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &mut App) {
-    let block = Block::default().borders(Borders::ALL).title("Map");
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(75), Constraint::Percentage(25)].as_ref())
+        .split(area);
+    let mut title =
+        "Map - h/j/k/l: pan, +/-: zoom, 0-3: world/europe/america/asia, Enter: show in table"
+            .to_owned();
+    if let Some(query) = app.active_search() {
+        title.push_str(&format!(" - Filter: {}", query));
+    }
+    let block = Block::default().borders(Borders::ALL).title(title);
+    let view = app.map_view;
+    let markers = app.map_markers();
+    let selected = nearest_marker(&markers, &view);
+    let colors = [app.settings.ui.accent_color, Color::Yellow, Color::Red];
+    let [west, east] = view.x_bounds();
     let canvas = Canvas::default()
         .block(block)
-        .x_bounds([-180.0, 180.0])
-        .y_bounds([-90.0, 90.0])
+        .x_bounds(view.x_bounds())
+        .y_bounds(view.y_bounds())
         .paint(|ctx| {
             ctx.draw(&Map {
                 color: app.settings.ui.primary_color,
                 resolution: MapResolution::High,
             });
             ctx.layer();
-            // The point struct expect (Longitude, Latitude)
-            let reversed_coords: Vec<(f64, f64)> = app
-                .map_locations
-                .iter()
-                .map(|(lat, lng)| (*lng, *lat))
-                .collect();
-            ctx.draw(&Points {
-                coords: &reversed_coords,
-                color: app.settings.ui.accent_color,
-            });
+            // Markers with more attempts are drawn larger and in warmer colors
+            for (intensity, color) in colors.iter().enumerate() {
+                let tier: Vec<&MapMarker> = markers
+                    .iter()
+                    .filter(|marker| marker.intensity() == intensity)
+                    .collect();
+                // The point struct expect (Longitude, Latitude)
+                let coords: Vec<(f64, f64)> = tier
+                    .iter()
+                    .map(|marker| (marker.longitude, marker.latitude))
+                    .collect();
+                ctx.draw(&Points {
+                    coords: &coords,
+                    color: *color,
+                });
+                for marker in tier.iter().filter(|_| intensity > 0) {
+                    ctx.draw(&Circle {
+                        x: marker.longitude,
+                        y: marker.latitude,
+                        radius: (east - west) * 0.004 * intensity as f64,
+                        color: *color,
+                    });
+                }
+            }
+            ctx.layer();
+            let cursor = match selected {
+                Some(marker) => (marker.longitude, marker.latitude),
+                None => view.center,
+            };
+            ctx.print(
+                cursor.0,
+                cursor.1,
+                Span::styled(
+                    "+",
+                    Style::default()
+                        .fg(app.settings.ui.primary_color)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            );
         });
-    frame.render_widget(canvas, area);
+    frame.render_widget(canvas, chunks[0]);
+    frame.render_widget(tooltip(selected, app), chunks[1]);
+}
+
+/// Country and ips of the marker under the cursor
+fn tooltip<'a>(marker: Option<&MapMarker>, app: &App) -> Paragraph<'a> {
+    let label_style = Style::default()
+        .fg(app.settings.ui.accent_color)
+        .add_modifier(Modifier::BOLD);
+    let lines = match marker {
+        Some(marker) => {
+            let mut lines = vec![
+                Line::from(vec![
+                    Span::styled("Country: ", label_style),
+                    Span::raw(marker.country.to_owned().unwrap_or_default()),
+                ]),
+                Line::from(vec![
+                    Span::styled("Location: ", label_style),
+                    Span::raw(format!("{:.2}, {:.2}", marker.latitude, marker.longitude)),
+                ]),
+                Line::from(vec![
+                    Span::styled("Attempts: ", label_style),
+                    Span::raw(marker.attempts().to_string()),
+                ]),
+                Line::from(""),
+            ];
+            lines.extend(
+                marker
+                    .ips
+                    .iter()
+                    .take(TOOLTIP_IPS)
+                    .map(|(ip, attempts)| Line::from(format!("{} ({})", ip, attempts))),
+            );
+            if marker.ips.len() > TOOLTIP_IPS {
+                lines.push(Line::from(format!(
+                    "and {} more",
+                    marker.ips.len() - TOOLTIP_IPS
+                )));
+            }
+            lines
+        }
+        None => vec![Line::from("No marker in view")],
+    };
+    Paragraph::new(lines).block(Block::default().title("Cursor").borders(Borders::ALL))
 }
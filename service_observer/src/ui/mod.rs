@@ -6,11 +6,21 @@ use ratatui::{
 
 use crate::app::App;
 
-use self::tab::tabs;
+use self::{
+    annotation::annotation_popup, blocking::block_popup, search::search_bar, tab::tabs,
+    time_range::time_range_popup,
+};
 
+mod annotation;
+mod blocking;
+mod dashboard;
+mod detail;
 mod map;
+mod search;
 mod tab;
 mod table;
+mod time_range;
+mod timeline;
 
 pub(crate) fn ui<B: Backend>(frame: &mut Frame<B>, app: &mut App) {
     let chunks = Layout::default()
@@ -26,4 +36,14 @@ pub(crate) fn ui<B: Backend>(frame: &mut Frame<B>, app: &mut App) {
         )
         .split(frame.size());
     tabs(frame, chunks[0], chunks[1], app);
+    search_bar(frame, chunks[2], app);
+    if let Some(editor) = &app.annotation_editor {
+        annotation_popup(frame, editor, app);
+    }
+    if let Some(preview) = &app.block_preview {
+        block_popup(frame, preview, app);
+    }
+    if let Some(picker) = &app.time_range_picker {
+        time_range_popup(frame, picker, app);
+    }
 }
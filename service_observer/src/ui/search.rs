@@ -0,0 +1,57 @@
+use ratatui::{
+    backend::Backend,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::app::App;
+
+/// Query of the `/` search and how many ips it matches
+pub(crate) fn search_bar<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
+    let accent = Style::default().fg(app.settings.ui.accent_color);
+    let primary = Style::default().fg(app.settings.ui.primary_color);
+    let (title, mut spans) = if app.editing_search {
+        (
+            "Search - Enter: apply, Esc: clear",
+            vec![Span::styled(format!("/{}", app.search_query), accent)],
+        )
+    } else if let Some(query) = app.active_search() {
+        let all = if app.show_allowlisted {
+            app.allowlisted_logs.len()
+        } else {
+            app.ssh_logs.len()
+        };
+        (
+            "Search - /: edit, Esc: clear",
+            vec![Span::styled(
+                format!(
+                    "Filter: {} ({} of {} ips)",
+                    query,
+                    app.visible_logs().len(),
+                    all
+                ),
+                accent,
+            )],
+        )
+    } else {
+        (
+            "Search",
+            vec![Span::styled(
+                "/: filter by ip prefix or network, country:, user:, event:, min:",
+                primary,
+            )],
+        )
+    };
+    if let Some(err) = &app.search_error {
+        spans.push(Span::styled(format!("  {}", err), primary));
+    }
+    if let Some(err) = &app.status_error {
+        spans.push(Span::styled(format!("  {}", err), primary));
+    }
+    let search = Paragraph::new(Line::from(spans))
+        .block(Block::default().title(title).borders(Borders::ALL));
+    frame.render_widget(search, area);
+}
@@ -1,15 +1,18 @@
 use ratatui::{
     backend::Backend,
-    layout::Rect,
+    layout::{Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Tabs},
     Frame,
 };
 
-use crate::app::App;
+use crate::app::{
+    constants::{DASHBOARD_TAB, MAP_TAB, TABLE_TAB, TIMELINE_TAB},
+    App,
+};
 
-use super::{map::map, table::table};
+use super::{dashboard::dashboard, detail::detail, map::map, table::table, timeline::timeline};
 
 pub(crate) fn tabs<B: Backend>(
     frame: &mut Frame<B>,
@@ -32,19 +35,35 @@ pub(crate) fn tabs<B: Backend>(
     let tab_highlight = Style::default()
         .add_modifier(Modifier::BOLD)
         .bg(app.settings.ui.secondary_color);
+    let progress = &app.lookup_progress;
+    let title = format!(
+        "Tabs - Logs {} (r: change) - Lookups {}/{} ({} failed)",
+        app.time_range, progress.done, progress.queued, progress.failed
+    );
     let tabs = Tabs::new(titels)
-        .block(Block::default().title("Tabs").borders(Borders::ALL))
+        .block(Block::default().title(title).borders(Borders::ALL))
         .select(app.tab_index)
         .style(tab_style)
         .highlight_style(tab_highlight);
     frame.render_widget(tabs, tab_area);
     match app.tab_index {
-        0 => {
-            table(frame, body_area, app);
-        }
-        1 => {
+        DASHBOARD_TAB => dashboard(frame, body_area, app),
+        TABLE_TAB => match app.detail.take() {
+            Some(opened_detail) => {
+                let chunks = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Percentage(40), Constraint::Percentage(60)].as_ref())
+                    .split(body_area);
+                table(frame, chunks[0], app);
+                detail(frame, chunks[1], &opened_detail, app);
+                app.detail = Some(opened_detail);
+            }
+            None => table(frame, body_area, app),
+        },
+        MAP_TAB => {
             map(frame, body_area, app);
         }
+        TIMELINE_TAB => timeline(frame, body_area, app),
         _ => unreachable!(),
     };
 }
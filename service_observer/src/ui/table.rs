@@ -1,40 +1,211 @@
 use ratatui::{
     backend::Backend,
-    layout::{Constraint, Rect},
+    layout::{Constraint, Margin, Rect},
     style::{Modifier, Style},
-    widgets::{Block, Borders, Row, Table},
+    widgets::{Block, Borders, Row, Scrollbar, ScrollbarOrientation, ScrollbarState, Table},
     Frame,
 };
 
-use crate::app::App;
+use chrono::{DateTime, Local};
+use crawler::{GeolocationRecord, Reputation};
+use ip_blocking::Fail2banBan;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::app::{table::SortColumn, App};
+
+/// Header of each column and the sort column it belongs to
+const COLUMNS: [(&str, Option<SortColumn>); 12] = [
+    ("IP", Some(SortColumn::Ip)),
+    ("Hostname", Some(SortColumn::Hostname)),
+    ("Country", Some(SortColumn::Country)),
+    ("ASN / Org", Some(SortColumn::Org)),
+    ("Logs", Some(SortColumn::Logs)),
+    ("First seen", Some(SortColumn::FirstSeen)),
+    ("Last seen", Some(SortColumn::LastSeen)),
+    ("Users", Some(SortColumn::Usernames)),
+    ("Logins", Some(SortColumn::Logins)),
+    ("Reputation", Some(SortColumn::Reputation)),
+    ("Tags", None),
+    ("Fail2ban", None),
+];
 
 pub(crate) fn table<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &mut App) {
     let normal_style = Style::default().bg(app.settings.ui.secondary_color);
     let selected_style = Style::default().add_modifier(Modifier::UNDERLINED);
-    let header = Row::new(vec!["IP", "Amount of Logs"])
-        .height(1)
-        .bottom_margin(1)
-        .style(normal_style);
-    let rows: Vec<Row> = app
-        .ssh_logs
-        .iter()
+    let recent_style = Style::default()
+        .fg(app.settings.ui.accent_color)
+        .add_modifier(Modifier::BOLD);
+    let header = Row::new(COLUMNS.iter().map(|(title, column)| {
+        match column.filter(|column| *column == app.table_sort.column) {
+            Some(_) if app.table_sort.descending => format!("{} ▼", title),
+            Some(_) => format!("{} ▲", title),
+            None => title.to_string(),
+        }
+    }))
+    .height(1)
+    .bottom_margin(1)
+    .style(normal_style);
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|now| now.as_secs() as i64)
+        .unwrap_or_default();
+    let visible_logs = app.visible_logs();
+    let row_amount = visible_logs.len();
+    let rows: Vec<Row> = visible_logs
+        .into_iter()
         .map(|item| {
-            let test = vec![item.0.to_string(), item.2.to_string()];
-            Row::new(test)
+            let reputation = app
+                .reputations
+                .get(&item.0)
+                .map(format_reputation)
+                .unwrap_or_default();
+            let hostname = app
+                .enrichments
+                .get(&item.0)
+                .and_then(|enrichment| enrichment.ptr.to_owned())
+                .unwrap_or_default();
+            let location = app.locations.get(&item.0);
+            let country = match location.and_then(|location| location.country_name.as_deref()) {
+                Some(country) => country.to_owned(),
+                None if app.unknown_locations.contains(&item.0) => "unknown".to_owned(),
+                None => String::new(),
+            };
+            let org = location.map(format_org).unwrap_or_default();
+            let stats = app.row_stats.get(&item.0).cloned().unwrap_or_default();
+            let tags = app
+                .annotations
+                .get(&item.0)
+                .map(|annotation| annotation.tags.join(", "))
+                .unwrap_or_default();
+            let ip = if app.banned.contains(&item.0) {
+                format!("{} (banned)", item.0)
+            } else {
+                item.0.to_string()
+            };
+            let fail2ban = app
+                .fail2ban_bans
+                .get(&item.0)
+                .map(|bans| format_fail2ban(bans, now))
+                .unwrap_or_default();
+            let test = vec![
+                ip,
+                hostname,
+                country,
+                org,
+                item.2.to_string(),
+                stats.first_seen.map(format_seen).unwrap_or_default(),
+                stats.last_seen.map(format_seen).unwrap_or_default(),
+                stats.usernames.to_string(),
+                stats.logins.to_string(),
+                reputation,
+                tags,
+                fail2ban,
+            ];
+            if app.is_recently_active(&item.0) {
+                Row::new(test).style(recent_style)
+            } else {
+                Row::new(test)
+            }
         })
         .collect();
+    let mut title = if app.show_allowlisted {
+        "Allowlisted SSH Logs By IP".to_owned()
+    } else {
+        format!(
+            "SSH Logs By IP ({} allowlisted hidden)",
+            app.allowlisted_logs.len()
+        )
+    };
+    if let Some(tag) = &app.tag_filter {
+        title.push_str(&format!(" - Tag: {}", tag));
+    }
+    if let Some(query) = app.active_search() {
+        title.push_str(&format!(" - Filter: {}", query));
+    }
     let ip_table = Table::new(rows)
         .header(header)
-        .block(
-            Block::default()
-                .title("SSH Logs By IP")
-                .borders(Borders::ALL),
-        )
+        .block(Block::default().title(title).borders(Borders::ALL))
         .highlight_style(selected_style)
         .widths(&[
-            Constraint::Percentage(50),
-            Constraint::Length(30),
-            Constraint::Min(10),
+            Constraint::Length(26),
+            Constraint::Percentage(12),
+            Constraint::Length(14),
+            Constraint::Percentage(12),
+            Constraint::Length(6),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(7),
+            Constraint::Length(8),
+            Constraint::Percentage(12),
+            Constraint::Min(8),
+            Constraint::Percentage(10),
         ]);
+    // Borders, header and its margin
+    app.table_page_size = usize::from(area.height.saturating_sub(4)).max(1);
     frame.render_stateful_widget(ip_table, area, &mut app.ssh_table_state);
+    let mut scrollbar_state = ScrollbarState::default()
+        .content_length(row_amount)
+        .position(app.ssh_table_state.selected().unwrap_or_default());
+    frame.render_stateful_widget(
+        Scrollbar::new(ScrollbarOrientation::VerticalRight),
+        area.inner(&Margin {
+            vertical: 1,
+            horizontal: 0,
+        }),
+        &mut scrollbar_state,
+    );
+}
+
+/// Formats journald's microseconds since the unix epoch as e.g. `12-24 18:03` in local time
+fn format_seen(micros: i64) -> String {
+    DateTime::from_timestamp(micros.div_euclid(1_000_000), 0)
+        .map(|timestamp| {
+            timestamp
+                .with_timezone(&Local)
+                .format("%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default()
+}
+
+/// Formats the network owner as e.g. `AS64500 Example Hosting`
+fn format_org(location: &GeolocationRecord) -> String {
+    [location.asn.as_deref(), location.org.as_deref()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Formats the bans as e.g. `sshd 1h 20m, recidive permanent`
+fn format_fail2ban(bans: &[Fail2banBan], now: i64) -> String {
+    bans.iter()
+        .map(|ban| match ban.until {
+            Some(until) => {
+                let minutes_left = (until - now).max(0) / 60;
+                format!("{} {}h {}m", ban.jail, minutes_left / 60, minutes_left % 60)
+            }
+            None => format!("{} permanent", ban.jail),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Formats the reputation as e.g. `87% (12 reports) spamhaus_drop, tor_exit`
+fn format_reputation(reputation: &Reputation) -> String {
+    let abuse = reputation
+        .abuse
+        .as_ref()
+        .map(|abuse| {
+            format!(
+                "{}% ({} reports)",
+                abuse.abuse_confidence_score, abuse.total_reports
+            )
+        })
+        .unwrap_or_else(|| "-".to_owned());
+    if reputation.blocklists.is_empty() {
+        abuse
+    } else {
+        format!("{} {}", abuse, reputation.blocklists.join(", "))
+    }
 }
@@ -0,0 +1,84 @@
+use ratatui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+    Frame,
+};
+
+use crate::app::{
+    time_range::{TimeRangeField, TimeRangePicker, TimeRangePreset},
+    App,
+};
+
+use super::annotation::centered_rect;
+
+pub(crate) fn time_range_popup<B: Backend>(
+    frame: &mut Frame<B>,
+    picker: &TimeRangePicker,
+    app: &App,
+) {
+    let area = centered_rect(50, 50, frame.size());
+    let title = if picker.editing {
+        "Time range - Tab: switch field, Enter: reload, Esc: cancel"
+    } else {
+        "Time range - Up/Down: choose, Enter: reload, Esc: cancel"
+    };
+    let block = Block::default().title(title).borders(Borders::ALL);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints(
+            [
+                Constraint::Length(TimeRangePreset::ALL.len() as u16),
+                Constraint::Length(3),
+                Constraint::Length(3),
+                Constraint::Min(1),
+            ]
+            .as_ref(),
+        )
+        .split(area);
+    let accent = Style::default().fg(app.settings.ui.accent_color);
+    let primary = Style::default().fg(app.settings.ui.primary_color);
+    let presets: Vec<ListItem> = TimeRangePreset::ALL
+        .iter()
+        .map(|preset| {
+            let style = if *preset == picker.preset() {
+                accent.add_modifier(Modifier::BOLD)
+            } else {
+                primary
+            };
+            ListItem::new(preset.as_str()).style(style)
+        })
+        .collect();
+    let field_style = |field: TimeRangeField| {
+        if picker.editing && picker.field == field {
+            accent
+        } else {
+            primary
+        }
+    };
+    let since = Paragraph::new(picker.since.as_str()).block(
+        Block::default()
+            .title("Since (journalctl time, e.g. -2h or 2023-12-24 18:00)")
+            .borders(Borders::ALL)
+            .border_style(field_style(TimeRangeField::Since)),
+    );
+    let until = Paragraph::new(picker.until.as_str()).block(
+        Block::default()
+            .title("Until (empty for now)")
+            .borders(Borders::ALL)
+            .border_style(field_style(TimeRangeField::Until)),
+    );
+    let error = Paragraph::new(Line::styled(
+        picker.error.to_owned().unwrap_or_default(),
+        accent,
+    ));
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+    frame.render_widget(List::new(presets), chunks[0]);
+    frame.render_widget(since, chunks[1]);
+    frame.render_widget(until, chunks[2]);
+    frame.render_widget(error, chunks[3]);
+}
@@ -0,0 +1,130 @@
+use chrono::{DateTime, Local};
+use ratatui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    symbols,
+    text::Span,
+    widgets::{Axis, Bar, BarChart, BarGroup, Block, Borders, Chart, Dataset, GraphType},
+    Frame,
+};
+
+use crate::app::{timeline::BucketSize, App};
+
+/// Width of a bar group in the bar chart, two bars and the gap to the next group
+const GROUP_WIDTH: u16 = 5;
+
+/// Failed and successful authentications over the loaded time range
+pub(crate) fn timeline<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)].as_ref())
+        .split(area);
+    let failed_style = Style::default().fg(app.settings.ui.accent_color);
+    let successful_style = Style::default().fg(app.settings.ui.primary_color);
+    let bucket = app.timeline_bucket;
+    let buckets = &app.timeline;
+
+    let failed: Vec<(f64, f64)> = buckets
+        .iter()
+        .map(|bucket| (bucket.start as f64, bucket.failed as f64))
+        .collect();
+    let successful: Vec<(f64, f64)> = buckets
+        .iter()
+        .map(|bucket| (bucket.start as f64, bucket.successful as f64))
+        .collect();
+    let first = buckets
+        .first()
+        .map(|bucket| bucket.start)
+        .unwrap_or_default();
+    let last = buckets
+        .last()
+        .map(|bucket| bucket.start)
+        .unwrap_or_default();
+    let max = buckets
+        .iter()
+        .map(|bucket| bucket.failed.max(bucket.successful))
+        .max()
+        .unwrap_or_default()
+        .max(1);
+    let datasets = vec![
+        Dataset::default()
+            .name("failed")
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(failed_style)
+            .data(&failed),
+        Dataset::default()
+            .name("successful")
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(successful_style)
+            .data(&successful),
+    ];
+    let chart = Chart::new(datasets)
+        .block(
+            Block::default()
+                .title(format!(
+                    "Authentications per {} - +/-: bucket size",
+                    bucket.as_str()
+                ))
+                .borders(Borders::ALL),
+        )
+        .x_axis(
+            Axis::default()
+                .bounds([first as f64, (last as f64).max(first as f64 + 1.0)])
+                .labels(vec![
+                    Span::raw(format_bucket(first, bucket)),
+                    Span::raw(format_bucket(first + (last - first) / 2, bucket)),
+                    Span::raw(format_bucket(last, bucket)),
+                ]),
+        )
+        .y_axis(Axis::default().bounds([0.0, max as f64]).labels(vec![
+            Span::raw("0"),
+            Span::raw((max / 2).to_string()),
+            Span::raw(max.to_string()),
+        ]));
+    frame.render_widget(chart, chunks[0]);
+
+    // The most recent buckets which fit into the chart
+    let shown = buckets
+        .len()
+        .min(usize::from(chunks[1].width.saturating_sub(2) / GROUP_WIDTH));
+    let mut bar_chart = BarChart::default()
+        .block(
+            Block::default()
+                .title("Latest buckets - failed / successful")
+                .borders(Borders::ALL),
+        )
+        .bar_width(2)
+        .bar_gap(0)
+        .group_gap(GROUP_WIDTH - 4);
+    for bucket_counts in &buckets[buckets.len() - shown..] {
+        let bars = [
+            Bar::default()
+                .value(bucket_counts.failed)
+                .style(failed_style),
+            Bar::default()
+                .value(bucket_counts.successful)
+                .style(successful_style),
+        ];
+        bar_chart = bar_chart.data(
+            BarGroup::default()
+                .label(format_bucket(bucket_counts.start, bucket).into())
+                .bars(&bars),
+        );
+    }
+    frame.render_widget(bar_chart, chunks[1]);
+}
+
+/// Formats the start of a bucket in local time, as precise as the bucket size
+fn format_bucket(start: i64, bucket: BucketSize) -> String {
+    let format = match bucket {
+        BucketSize::Minute => "%H:%M",
+        BucketSize::Hour => "%d %Hh",
+        BucketSize::Day => "%m-%d",
+    };
+    DateTime::from_timestamp(start, 0)
+        .map(|start| start.with_timezone(&Local).format(format).to_string())
+        .unwrap_or_default()
+}
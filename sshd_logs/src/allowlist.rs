@@ -0,0 +1,125 @@
+use ipnet::Ipv4Net;
+use std::net::Ipv4Addr;
+
+use crate::SshLogParserError;
+
+/// Trusted networks, e.g. office ranges or monitoring probes, which are left out of the analysis
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Allowlist {
+    networks: Vec<Ipv4Net>,
+    /// Private, loopback and link-local addresses are never looked up
+    pub exclude_private: bool,
+}
+
+impl Default for Allowlist {
+    fn default() -> Self {
+        Self {
+            networks: Vec::new(),
+            exclude_private: true,
+        }
+    }
+}
+
+impl Allowlist {
+    /// Accepts networks in cidr notation and plain ips
+    pub fn parse<S: AsRef<str>>(entries: &[S]) -> Result<Self, SshLogParserError> {
+        let networks = entries
+            .iter()
+            .map(|entry| parse_network(entry.as_ref()))
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            networks,
+            ..Default::default()
+        })
+    }
+
+    pub fn with_exclude_private(mut self, exclude_private: bool) -> Self {
+        self.exclude_private = exclude_private;
+        self
+    }
+
+    pub fn contains(&self, ipv4: &Ipv4Addr) -> bool {
+        self.networks.iter().any(|network| network.contains(ipv4))
+    }
+
+    /// Whether the ip must not be sent to external lookup services
+    pub fn excludes_lookup(&self, ipv4: &Ipv4Addr) -> bool {
+        self.contains(ipv4) || (self.exclude_private && is_private(ipv4))
+    }
+
+    /// Adds the single ip, returns false if it was allowed before
+    pub fn insert(&mut self, ipv4: Ipv4Addr) -> bool {
+        if self.contains(&ipv4) {
+            return false;
+        }
+        self.networks.push(Ipv4Net::from(ipv4));
+        true
+    }
+
+    /// Removes the entry of the single ip, networks containing it are kept
+    pub fn remove(&mut self, ipv4: &Ipv4Addr) -> bool {
+        let len = self.networks.len();
+        self.networks
+            .retain(|network| *network != Ipv4Net::from(*ipv4));
+        self.networks.len() != len
+    }
+
+    pub fn networks(&self) -> Vec<String> {
+        self.networks.iter().map(ToString::to_string).collect()
+    }
+}
+
+fn parse_network(entry: &str) -> Result<Ipv4Net, SshLogParserError> {
+    let entry = entry.trim();
+    entry
+        .parse::<Ipv4Net>()
+        .map(|network| network.trunc())
+        .or_else(|_| entry.parse::<Ipv4Addr>().map(Ipv4Net::from))
+        .map_err(|_| SshLogParserError::InvalidNetwork(entry.to_owned()))
+}
+
+/// Addresses which are not reachable from the internet
+pub fn is_private(ipv4: &Ipv4Addr) -> bool {
+    ipv4.is_private()
+        || ipv4.is_loopback()
+        || ipv4.is_link_local()
+        || ipv4.is_unspecified()
+        || ipv4.is_broadcast()
+        || is_shared(ipv4)
+}
+
+/// Shared address space of carrier-grade NATs, 100.64.0.0/10 from RFC 6598
+fn is_shared(ipv4: &Ipv4Addr) -> bool {
+    let [first, second, ..] = ipv4.octets();
+    first == 100 && (second & 0b1100_0000) == 64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_contain_configured_networks() {
+        let allowlist = Allowlist::parse(&["203.0.113.0/24", "198.51.100.7"]).unwrap();
+        assert!(allowlist.contains(&Ipv4Addr::new(203, 0, 113, 42)));
+        assert!(allowlist.contains(&Ipv4Addr::new(198, 51, 100, 7)));
+        assert!(!allowlist.contains(&Ipv4Addr::new(198, 51, 100, 8)));
+    }
+
+    #[test]
+    fn should_exclude_private_ranges_from_lookups() {
+        let allowlist = Allowlist::default();
+        assert!(allowlist.excludes_lookup(&Ipv4Addr::new(192, 168, 1, 10)));
+        assert!(allowlist.excludes_lookup(&Ipv4Addr::new(127, 0, 0, 1)));
+        assert!(allowlist.excludes_lookup(&Ipv4Addr::new(100, 64, 3, 1)));
+        assert!(!allowlist.excludes_lookup(&Ipv4Addr::new(203, 0, 113, 42)));
+        assert!(!allowlist
+            .with_exclude_private(false)
+            .excludes_lookup(&Ipv4Addr::new(10, 0, 0, 1)));
+    }
+
+    #[test]
+    fn should_reject_invalid_networks() {
+        assert!(Allowlist::parse(&["203.0.113.0/33"]).is_err());
+    }
+}
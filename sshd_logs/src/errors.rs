@@ -6,4 +6,6 @@ pub enum SshLogParserError {
     LogExtraction,
     #[error("Invalid ip")]
     InvalidIpAddr(#[from] AddrParseError),
+    #[error("Invalid allowlist entry: {0}")]
+    InvalidNetwork(String),
 }
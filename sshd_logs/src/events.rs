@@ -0,0 +1,195 @@
+use journal_parser::models::LogEntry;
+use regex::Regex;
+use std::{fmt, net::Ipv4Addr, str::FromStr, sync::OnceLock};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SshEventKind {
+    FailedPassword,
+    FailedPublickey,
+    InvalidUser,
+    Accepted,
+    Disconnected,
+    ConnectionClosed,
+    Other,
+}
+
+impl SshEventKind {
+    /// Authentication attempts, `Invalid user` lines are followed by a failure or a closed
+    /// connection and are not counted twice
+    pub fn is_attempt(&self) -> bool {
+        matches!(
+            self,
+            Self::FailedPassword | Self::FailedPublickey | Self::Accepted
+        )
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::FailedPassword => "failed_password",
+            Self::FailedPublickey => "failed_publickey",
+            Self::InvalidUser => "invalid_user",
+            Self::Accepted => "accepted",
+            Self::Disconnected => "disconnected",
+            Self::ConnectionClosed => "connection_closed",
+            Self::Other => "other",
+        }
+    }
+}
+
+impl fmt::Display for SshEventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SshEventKind {
+    type Err = ();
+
+    fn from_str(kind: &str) -> Result<Self, Self::Err> {
+        match kind {
+            "failed_password" => Ok(Self::FailedPassword),
+            "failed_publickey" => Ok(Self::FailedPublickey),
+            "invalid_user" => Ok(Self::InvalidUser),
+            "accepted" => Ok(Self::Accepted),
+            "disconnected" => Ok(Self::Disconnected),
+            "connection_closed" => Ok(Self::ConnectionClosed),
+            "other" => Ok(Self::Other),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Single sshd log line that mentions an ipv4
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SshEvent {
+    pub ipv4: Ipv4Addr,
+    /// Microseconds since the unix epoch, as reported by journald
+    pub timestamp: i64,
+    pub user: Option<String>,
+    pub port: Option<u16>,
+    pub kind: SshEventKind,
+    pub message: String,
+    pub cursor: String,
+}
+
+impl SshEvent {
+    pub fn from_log(log: &LogEntry) -> Option<Self> {
+        let parsed = parse_message(&log.message)?;
+        Some(Self {
+            ipv4: parsed.ipv4,
+            timestamp: log.realtime_timestamp.parse().ok()?,
+            user: parsed.user,
+            port: parsed.port,
+            kind: parsed.kind,
+            message: log.message.to_owned(),
+            cursor: log.cursor.to_owned(),
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct ParsedMessage {
+    ipv4: Ipv4Addr,
+    user: Option<String>,
+    port: Option<u16>,
+    kind: SshEventKind,
+}
+
+fn message_regex() -> &'static Regex {
+    static MESSAGE_REGEX: OnceLock<Regex> = OnceLock::new();
+    MESSAGE_REGEX.get_or_init(|| {
+        Regex::new(
+            r"(?:(?:for|user) (?:invalid user )?(?P<user>\S+) (?:from )?|(?:from|by) )(?P<ip>\d{1,3}(?:\.\d{1,3}){3})(?: port (?P<port>\d+))?",
+        )
+        .unwrap()
+    })
+}
+
+fn parse_message(message: &str) -> Option<ParsedMessage> {
+    let captures = message_regex().captures(message)?;
+    let ipv4 = captures.name("ip")?.as_str().parse().ok()?;
+    let kind = if message.starts_with("Failed password") {
+        SshEventKind::FailedPassword
+    } else if message.starts_with("Failed publickey") {
+        SshEventKind::FailedPublickey
+    } else if message.starts_with("Invalid user") {
+        SshEventKind::InvalidUser
+    } else if message.starts_with("Accepted") {
+        SshEventKind::Accepted
+    } else if message.starts_with("Disconnected from") || message.starts_with("Received disconnect")
+    {
+        SshEventKind::Disconnected
+    } else if message.starts_with("Connection closed") {
+        SshEventKind::ConnectionClosed
+    } else {
+        SshEventKind::Other
+    };
+    Some(ParsedMessage {
+        ipv4,
+        user: captures.name("user").map(|user| user.as_str().to_owned()),
+        port: captures
+            .name("port")
+            .and_then(|port| port.as_str().parse().ok()),
+        kind,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_failed_password() {
+        let parsed = parse_message(
+            "Failed password for invalid user admin from 203.0.113.7 port 52312 ssh2",
+        )
+        .unwrap();
+        assert_eq!(
+            parsed,
+            ParsedMessage {
+                ipv4: Ipv4Addr::new(203, 0, 113, 7),
+                user: Some("admin".to_owned()),
+                port: Some(52312),
+                kind: SshEventKind::FailedPassword,
+            }
+        );
+    }
+
+    #[test]
+    fn should_parse_invalid_user() {
+        let parsed = parse_message("Invalid user oracle from 198.51.100.23 port 40022").unwrap();
+        assert_eq!(parsed.kind, SshEventKind::InvalidUser);
+        assert_eq!(parsed.user.as_deref(), Some("oracle"));
+        assert_eq!(parsed.port, Some(40022));
+    }
+
+    #[test]
+    fn should_parse_events_without_user() {
+        let parsed =
+            parse_message("Connection closed by 198.51.100.23 port 40022 [preauth]").unwrap();
+        assert_eq!(parsed.kind, SshEventKind::ConnectionClosed);
+        assert_eq!(parsed.user, None);
+        assert_eq!(parsed.ipv4, Ipv4Addr::new(198, 51, 100, 23));
+    }
+
+    #[test]
+    fn should_parse_disconnects_of_invalid_users() {
+        let parsed =
+            parse_message("Disconnected from invalid user admin 203.0.113.7 port 52312 [preauth]")
+                .unwrap();
+        assert_eq!(parsed.kind, SshEventKind::Disconnected);
+        assert_eq!(parsed.user.as_deref(), Some("admin"));
+        assert_eq!(parsed.port, Some(52312));
+    }
+
+    #[test]
+    fn should_skip_messages_without_ip() {
+        assert!(parse_message("Server listening on 0.0.0.0 port 22.").is_none());
+    }
+
+    #[test]
+    fn should_round_trip_event_kinds() {
+        let kind = SshEventKind::FailedPublickey;
+        assert_eq!(kind.as_str().parse(), Ok(kind));
+    }
+}
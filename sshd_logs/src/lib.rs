@@ -1,5 +1,9 @@
+mod allowlist;
 mod errors;
+mod events;
 mod logs;
 
+pub use allowlist::{is_private, Allowlist};
 pub use errors::SshLogParserError;
-pub use logs::SshdLogs;
+pub use events::{SshEvent, SshEventKind};
+pub use logs::{LogsByIp, SshdLogs};
@@ -3,10 +3,19 @@ use rayon::prelude::*;
 use regex::Regex;
 use std::{collections::HashMap, net::Ipv4Addr};
 
-use crate::SshLogParserError;
+use crate::{Allowlist, SshEvent, SshLogParserError};
+
+pub type LogsByIp<'a> = HashMap<Ipv4Addr, Vec<&'a LogEntry>>;
 
 pub trait SshdLogs {
     fn by_ips(&self) -> Result<HashMap<Ipv4Addr, Vec<&LogEntry>>, SshLogParserError>;
+    /// Like [`SshdLogs::by_ips`], split into the analysed and the allowlisted ips
+    fn by_ips_with_allowlist(
+        &self,
+        allowlist: &Allowlist,
+    ) -> Result<(LogsByIp<'_>, LogsByIp<'_>), SshLogParserError>;
+    /// Logs which mention an ipv4, parsed into events
+    fn events(&self) -> Result<Vec<SshEvent>, SshLogParserError>;
 }
 
 impl SshdLogs for LogOutput {
@@ -58,6 +67,27 @@ impl SshdLogs for LogOutput {
                 },
             ))
     }
+
+    fn by_ips_with_allowlist(
+        &self,
+        allowlist: &Allowlist,
+    ) -> Result<(LogsByIp<'_>, LogsByIp<'_>), SshLogParserError> {
+        Ok(self
+            .by_ips()?
+            .into_iter()
+            .partition(|(ip, _)| !allowlist.contains(ip)))
+    }
+
+    fn events(&self) -> Result<Vec<SshEvent>, SshLogParserError> {
+        if self.status == OutputStatus::FAILED {
+            return Err(SshLogParserError::LogExtraction);
+        };
+        Ok(self
+            .logs
+            .par_iter()
+            .filter_map(SshEvent::from_log)
+            .collect())
+    }
 }
 
 #[cfg(test)]